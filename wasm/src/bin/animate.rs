@@ -0,0 +1,42 @@
+// Writes every turn of one case as a single animated SVG to stdout.
+use clap::Parser;
+use rust::input::parse_input;
+use rust::output::parse_output;
+use rust::theme::Theme;
+use rust::vis::vis_animation;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+    /// Path to the output file
+    output: String,
+    /// Case to animate
+    #[arg(long, default_value_t = 0)]
+    case: usize,
+    /// Colour theme: light, colour-blind or dark
+    #[arg(long, default_value = "light")]
+    theme: String,
+    /// Seconds each turn stays on screen
+    #[arg(long, default_value_t = 0.5)]
+    seconds: f64,
+}
+
+fn main() {
+    let args = Args::parse();
+    let theme =
+        Theme::by_name(&args.theme).unwrap_or_else(|| panic!("unknown theme: {}", args.theme));
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+    let output = std::fs::read_to_string(&args.output)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.output));
+    let output = parse_output(&output, input.c, input.t);
+    let (score, err, svg) = vis_animation(&input, &output, args.case, args.seconds, &theme);
+    if !err.is_empty() {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+    print!("{}", svg);
+    eprintln!("Score = {}", score);
+}
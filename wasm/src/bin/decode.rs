@@ -0,0 +1,35 @@
+use clap::Parser;
+use rust::decoder;
+use rust::input::parse_input;
+use rust::score::calculate_score;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    #[arg(required_unless_present = "list")]
+    input: Option<String>,
+    /// Name of the decoder to run
+    #[arg(long, default_value = "mwpm")]
+    decoder: String,
+    /// List the available decoders and exit
+    #[arg(long)]
+    list: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.list {
+        for decoder in decoder::decoders() {
+            println!("{}", decoder.name());
+        }
+        return;
+    }
+    let path = args.input.unwrap();
+    let input = std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("no such file: {}", path));
+    let input = parse_input(&input);
+    let decoder =
+        decoder::find(&args.decoder).unwrap_or_else(|| panic!("unknown decoder: {}", args.decoder));
+    let output = decoder::run(decoder.as_ref(), &input);
+    print!("{}", output.to_canonical_string());
+    eprintln!("Score = {}", calculate_score(&input, &output));
+}
@@ -0,0 +1,39 @@
+use clap::Parser;
+use rust::dem::{check_matrix, observable_matrix, priors, to_dem};
+use rust::input::parse_input;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+    /// Write the parity-check matrix (Matrix Market) to this file
+    #[arg(long)]
+    check_matrix: Option<String>,
+    /// Write the logical observable matrix (Matrix Market) to this file
+    #[arg(long)]
+    observable_matrix: Option<String>,
+    /// Write the mechanism probabilities (Matrix Market) to this file
+    #[arg(long)]
+    priors: Option<String>,
+}
+
+fn write(path: &str, contents: String) {
+    std::fs::write(path, contents).unwrap_or_else(|_| panic!("failed to write {}", path));
+}
+
+fn main() {
+    let args = Args::parse();
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+    if let Some(path) = &args.check_matrix {
+        write(path, check_matrix(&input));
+    }
+    if let Some(path) = &args.observable_matrix {
+        write(path, observable_matrix(&input));
+    }
+    if let Some(path) = &args.priors {
+        write(path, priors(&input));
+    }
+    print!("{}", to_dem(&input));
+}
@@ -0,0 +1,51 @@
+// Checks case by case whether two outputs for the same input are in the same
+// stabilizer coset. Exits with status 1 if any case is not.
+use clap::Parser;
+use rust::equivalence::{compare, Relation};
+use rust::input::parse_input;
+use rust::output::{parse_output, validate_output, Output};
+use rust::score::calculate_score;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+    /// Path to the first output file
+    a: String,
+    /// Path to the second output file
+    b: String,
+}
+
+fn read_output(path: &str, input: &rust::input::Input) -> Output {
+    let output = std::fs::read_to_string(path).unwrap_or_else(|_| panic!("no such file: {}", path));
+    let output = parse_output(&output, input.c, input.t);
+    validate_output(input, &output).unwrap_or_else(|err| panic!("{}: {}", path, err));
+    output
+}
+
+fn main() {
+    let args = Args::parse();
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+    let a = read_output(&args.a, &input);
+    let b = read_output(&args.b, &input);
+
+    let relations = compare(&input, &a, &b);
+    for (case, relation) in relations.iter().enumerate() {
+        println!("case {}: {}", case, relation);
+    }
+    let different = relations
+        .iter()
+        .filter(|&&relation| relation != Relation::Equivalent)
+        .count();
+    println!("{} of {} cases differ", different, input.t);
+    eprintln!(
+        "Score = {} / {}",
+        calculate_score(&input, &a),
+        calculate_score(&input, &b)
+    );
+    if different > 0 {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,41 @@
+// Prints what happened to one site of a case in every cycle: errors,
+// corrections and frame state of a data qubit, or residual parity, residual
+// measured value and measurement errors of an ancilla.
+use clap::Parser;
+use rust::input::parse_input;
+use rust::output::{parse_output, validate_output};
+use rust::timeline::site_history;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+    /// Path to the output file
+    output: String,
+    /// Row of the site
+    i: usize,
+    /// Column of the site
+    j: usize,
+    /// Case of the input
+    #[arg(long, default_value_t = 0)]
+    case: usize,
+}
+
+fn main() {
+    let args = Args::parse();
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+    let output = std::fs::read_to_string(&args.output)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.output));
+    let output = parse_output(&output, input.c, input.t);
+    validate_output(&input, &output).unwrap_or_else(|err| panic!("{}: {}", args.output, err));
+
+    match site_history(&input, &output, args.case, args.i, args.j) {
+        Ok(history) => print!("{}", history),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
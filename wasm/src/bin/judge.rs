@@ -0,0 +1,160 @@
+// Interactive judge: the solver only ever sees the syndromes measured so far.
+//
+// Protocol (all lines on the solver's stdin/stdout):
+//   judge  -> solver: "d c e_d e_m t"
+//   for each case, for each cycle:
+//     judge  -> solver: k, then k lines "i j" of the ancillas measured as 1
+//     solver -> judge:  n_x, then n_x lines "x y" of X corrections,
+//                       n_z, then n_z lines "x y" of Z corrections
+// Measured values never include the solver's own corrections. A solver that
+// does not answer a cycle within --timeout seconds is stopped and scores 0.
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use rust::input::parse_input;
+use rust::output::validate_output;
+use rust::score::calculate_score;
+use rust::sdk::OutputBuilder;
+use rust::syndrome::Syndromes;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+    /// Also write the collected corrections to this file
+    #[arg(long)]
+    output: Option<String>,
+    /// Seconds the solver may take to answer each cycle
+    #[arg(long, default_value_t = 10.0)]
+    timeout: f64,
+    /// Solver command and its arguments
+    #[arg(trailing_var_arg = true, required = true)]
+    command: Vec<String>,
+}
+
+// Tokens of the solver's output, read line by line on another thread so
+// that waiting for them can time out.
+struct Tokens {
+    lines: Receiver<std::io::Result<String>>,
+    buffer: Vec<String>,
+    deadline: Instant,
+}
+
+impl Tokens {
+    fn new(reader: impl BufRead + Send + 'static) -> Tokens {
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Tokens {
+            lines,
+            buffer: vec![],
+            deadline: Instant::now(),
+        }
+    }
+
+    fn next(&mut self) -> Result<usize, String> {
+        while self.buffer.is_empty() {
+            let timeout = self.deadline.saturating_duration_since(Instant::now());
+            match self.lines.recv_timeout(timeout) {
+                Ok(Ok(line)) => {
+                    self.buffer = line.split_whitespace().rev().map(String::from).collect()
+                }
+                Ok(Err(err)) => return Err(err.to_string()),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err("the solver did not answer in time".to_string())
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("the solver closed its output".to_string())
+                }
+            }
+        }
+        let token = self.buffer.pop().unwrap();
+        token
+            .parse()
+            .map_err(|_| format!("expected a non-negative integer, got {}", token))
+    }
+
+    fn corrections(&mut self) -> Result<Vec<(usize, usize)>, String> {
+        let n = self.next()?;
+        let mut ret = vec![];
+        for _ in 0..n {
+            ret.push((self.next()?, self.next()?));
+        }
+        Ok(ret)
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+
+    let mut solver = Command::new(&args.command[0])
+        .args(&args.command[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|err| panic!("failed to start {}: {}", args.command[0], err));
+    let mut stdin = solver.stdin.take().unwrap();
+    let mut tokens = Tokens::new(BufReader::new(solver.stdout.take().unwrap()));
+    let timeout = Duration::from_secs_f64(args.timeout);
+
+    // the solver's answers are stored as read and validated at the end
+    let mut output = OutputBuilder::for_input(&input).build();
+    let result = (|| -> Result<(), String> {
+        let write_err = |err: std::io::Error| err.to_string();
+        writeln!(
+            stdin,
+            "{} {} {} {} {}",
+            input.d, input.c, input.e_d, input.e_m, input.t
+        )
+        .map_err(write_err)?;
+        for case in 0..input.t {
+            let syndromes = Syndromes::observe(&input, case);
+            for cycle in 0..input.c {
+                let mut lines = vec![];
+                for i in 0..input.d {
+                    for j in 0..input.d {
+                        if syndromes.measured[cycle][i][j] == 1 {
+                            lines.push(format!("{} {}\n", i, j));
+                        }
+                    }
+                }
+                write!(stdin, "{}\n{}", lines.len(), lines.concat()).map_err(write_err)?;
+                stdin.flush().map_err(write_err)?;
+                tokens.deadline = Instant::now() + timeout;
+                let at = |err: String| format!("case {} cycle {}: {}", case, cycle, err);
+                output.x_correction[case][cycle] = tokens.corrections().map_err(at)?;
+                output.z_correction[case][cycle] = tokens.corrections().map_err(at)?;
+            }
+        }
+        validate_output(&input, &output)
+    })();
+    drop(stdin);
+    if result.is_err() {
+        let _ = solver.kill();
+    }
+    let _ = solver.wait();
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, output.to_string())
+            .unwrap_or_else(|_| panic!("failed to write {}", path));
+    }
+    match result {
+        Ok(()) => eprintln!("Score = {}", calculate_score(&input, &output)),
+        Err(err) => {
+            eprintln!("{}", err);
+            eprintln!("Score = 0");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Rewrites an output into minimum-weight corrections with the same final
+// syndromes and logical class, and so the same score.
+use clap::Parser;
+use rust::input::parse_input;
+use rust::minimize::minimize;
+use rust::output::{parse_output, validate_output};
+use rust::score::calculate_score;
+
+/// Rewrite an output into minimum-weight corrections with the same score.
+///
+/// Every kept correction of a case is moved into its last cycle, so the
+/// output no longer says in which cycle a correction was made.
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+    /// Path to the output file to minimize
+    output: String,
+}
+
+fn main() {
+    let args = Args::parse();
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+    let output = std::fs::read_to_string(&args.output)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.output));
+    let output = parse_output(&output, input.c, input.t);
+    validate_output(&input, &output).unwrap_or_else(|err| panic!("{}", err));
+
+    let minimized = minimize(&input, &output);
+    print!("{}", minimized.output.to_canonical_string());
+    for case in 0..input.t {
+        eprintln!(
+            "case {}: {} -> {} corrections",
+            case, minimized.before[case], minimized.after[case]
+        );
+    }
+    eprintln!("removed {} corrections", minimized.removed());
+    eprintln!("Score = {}", calculate_score(&input, &minimized.output));
+}
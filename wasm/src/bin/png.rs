@@ -0,0 +1,118 @@
+// Renders turns of one case to PNG without a browser: a single turn to the
+// given file, or every turn to <path>/<turn>.png with the turn zero-padded.
+// With --summary it renders the grid of all cases to the given file instead,
+// with --heatmap one of the per-site aggregates over all cases. With --diff
+// the turns show the output next to a second one on the same lattice.
+use clap::Parser;
+use rust::heatmap::Aggregate;
+use rust::input::parse_input;
+use rust::output::parse_output;
+use rust::raster::Rasterizer;
+use rust::theme::Theme;
+use rust::vis::{vis_diff, vis_heatmap, vis_summary, vis_with, VisOptions};
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+    /// Path to the output file
+    output: String,
+    /// Case to render
+    #[arg(long, default_value_t = 0)]
+    case: usize,
+    /// Turn to render; every turn if omitted
+    #[arg(long)]
+    turn: Option<usize>,
+    /// PNG file for a single turn, directory for every turn
+    #[arg(long, short)]
+    out: String,
+    /// Draw the legend panel
+    #[arg(long)]
+    legend: bool,
+    /// Draw the header line with the state of the turn
+    #[arg(long)]
+    hud: bool,
+    /// Colour theme: light, colour-blind or dark
+    #[arg(long, default_value = "light")]
+    theme: String,
+    /// Render the outcome of every case instead of a case
+    #[arg(long)]
+    summary: bool,
+    /// Render a heatmap over all cases instead of a case: x-errors, z-errors,
+    /// measurement-errors, x-corrections or z-corrections
+    #[arg(long)]
+    heatmap: Option<String>,
+    /// Path to a second output file to compare the output with
+    #[arg(long)]
+    diff: Option<String>,
+    /// Pixels per SVG unit
+    #[arg(long, default_value_t = 1.0)]
+    scale: f32,
+}
+
+fn main() {
+    let args = Args::parse();
+    let theme =
+        Theme::by_name(&args.theme).unwrap_or_else(|| panic!("unknown theme: {}", args.theme));
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+    let output = std::fs::read_to_string(&args.output)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.output));
+    let output = parse_output(&output, input.c, input.t);
+
+    let rasterizer = Rasterizer::new();
+    let write = |(_, err, svg): (i64, String, String), path: &str| {
+        if !err.is_empty() {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+        let png = rasterizer
+            .to_png(&svg, args.scale)
+            .unwrap_or_else(|err| panic!("failed to render {}: {}", path, err));
+        std::fs::write(path, png).unwrap_or_else(|_| panic!("failed to write {}", path));
+    };
+    if args.summary {
+        write(vis_summary(&input, &output, &theme), &args.out);
+        return;
+    }
+    if let Some(name) = &args.heatmap {
+        let aggregate =
+            Aggregate::by_name(name).unwrap_or_else(|| panic!("unknown heatmap: {}", name));
+        write(vis_heatmap(&input, &output, aggregate, &theme), &args.out);
+        return;
+    }
+
+    let options = VisOptions {
+        case: args.case,
+        legend: args.legend,
+        hud: args.hud,
+        theme,
+    };
+    let second = args.diff.as_ref().map(|path| {
+        let second =
+            std::fs::read_to_string(path).unwrap_or_else(|_| panic!("no such file: {}", path));
+        parse_output(&second, input.c, input.t)
+    });
+    let render = |turn: usize, path: &str| match &second {
+        Some(second) => {
+            let (score, _, err, svg) =
+                vis_diff(&input, &output, second, args.case, turn, &options.theme);
+            write((score, err, svg), path)
+        }
+        None => write(vis_with(&input, &output, turn, &options), path),
+    };
+    match args.turn {
+        Some(turn) => render(turn, &args.out),
+        None => {
+            std::fs::create_dir_all(&args.out)
+                .unwrap_or_else(|_| panic!("failed to create {}", args.out));
+            let turns = input.c * 2 + 2;
+            let width = (turns - 1).to_string().len();
+            for turn in 0..turns {
+                render(turn, &format!("{}/{:0width$}.png", args.out, turn));
+            }
+            eprintln!("wrote {} turns to {}", turns, args.out);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use clap::Parser;
+use rust::decoder;
+use rust::sample::{sample, Estimate, SampleConfig};
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Data error rate
+    #[arg(long)]
+    e_d: f64,
+    /// Measurement error rate
+    #[arg(long)]
+    e_m: f64,
+    /// Code distance
+    #[arg(long, default_value_t = 21)]
+    d: usize,
+    /// Number of cycles per case
+    #[arg(long, default_value_t = 100)]
+    c: usize,
+    /// Name of the decoder to run
+    #[arg(long, default_value = "mwpm")]
+    decoder: String,
+    /// Seed of the case generator
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Maximum number of cases
+    #[arg(long, default_value_t = 10000)]
+    shots: usize,
+    /// Stop once the combined rate has this relative error
+    #[arg(long)]
+    target: Option<f64>,
+    /// Normal quantile of the confidence intervals
+    #[arg(long, default_value_t = 1.96)]
+    z: f64,
+}
+
+fn row(name: &str, estimate: &Estimate, z: f64) {
+    let (lo, hi) = estimate.wilson(z);
+    println!(
+        "{:<9} {:>8} / {:<8} {:.6} [{:.6}, {:.6}]",
+        name,
+        estimate.failures,
+        estimate.shots,
+        estimate.rate(),
+        lo,
+        hi
+    );
+}
+
+fn main() {
+    let args = Args::parse();
+    let decoder =
+        decoder::find(&args.decoder).unwrap_or_else(|| panic!("unknown decoder: {}", args.decoder));
+    let report = sample(
+        decoder.as_ref(),
+        &SampleConfig {
+            d: args.d,
+            c: args.c,
+            e_d: args.e_d,
+            e_m: args.e_m,
+            seed: args.seed,
+            max_shots: args.shots,
+            target: args.target,
+            z: args.z,
+        },
+    );
+    row("X", &report.x, args.z);
+    row("Z", &report.z, args.z);
+    row("combined", &report.combined, args.z);
+}
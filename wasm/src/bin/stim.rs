@@ -0,0 +1,22 @@
+use clap::Parser;
+use rust::input::parse_input;
+use rust::stim::{to_stim, Memory};
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+    /// Logical operator the memory experiment keeps: z or x
+    #[arg(long, default_value = "z")]
+    memory: String,
+}
+
+fn main() {
+    let args = Args::parse();
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+    let memory =
+        Memory::by_name(&args.memory).unwrap_or_else(|| panic!("unknown memory: {}", args.memory));
+    print!("{}", to_stim(&input, memory));
+}
@@ -0,0 +1,17 @@
+use clap::Parser;
+use rust::input::parse_input;
+use rust::syndrome::syndrome_stream;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Path to the input file
+    input: String,
+}
+
+fn main() {
+    let args = Args::parse();
+    let input = std::fs::read_to_string(&args.input)
+        .unwrap_or_else(|_| panic!("no such file: {}", args.input));
+    let input = parse_input(&input);
+    print!("{}", syndrome_stream(&input));
+}
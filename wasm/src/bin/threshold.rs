@@ -0,0 +1,92 @@
+use clap::Parser;
+use rust::decoder;
+use rust::theme::Theme;
+use rust::threshold::{crossings, plot, sweep, to_csv, SweepConfig};
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Code distances, comma separated
+    #[arg(long, value_delimiter = ',', default_value = "5,7,9")]
+    distances: Vec<usize>,
+    /// Data error rates, comma separated
+    #[arg(long, value_delimiter = ',', required = true)]
+    e_d: Vec<f64>,
+    /// Fixed measurement error rate (default: e_m_ratio * e_d)
+    #[arg(long)]
+    e_m: Option<f64>,
+    /// Measurement error rate relative to e_d
+    #[arg(long, default_value_t = 1.0)]
+    e_m_ratio: f64,
+    /// Number of cycles per case (default: d noisy cycles and the quiet ones)
+    #[arg(long)]
+    c: Option<usize>,
+    /// Name of the decoder to run
+    #[arg(long, default_value = "mwpm")]
+    decoder: String,
+    /// Seed of the case generator
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Maximum number of cases per point
+    #[arg(long, default_value_t = 10000)]
+    shots: usize,
+    /// Stop a point once its rate has this relative error
+    #[arg(long)]
+    target: Option<f64>,
+    /// Normal quantile of the confidence intervals
+    #[arg(long, default_value_t = 1.96)]
+    z: f64,
+    /// Write the CSV to this file instead of stdout
+    #[arg(long)]
+    csv: Option<String>,
+    /// Write the log-log plot to this file
+    #[arg(long)]
+    svg: Option<String>,
+    /// Colour theme of the plot: light, colour-blind or dark
+    #[arg(long, default_value = "light")]
+    theme: String,
+}
+
+fn main() {
+    let args = Args::parse();
+    let theme =
+        Theme::by_name(&args.theme).unwrap_or_else(|| panic!("unknown theme: {}", args.theme));
+    let decoder =
+        decoder::find(&args.decoder).unwrap_or_else(|| panic!("unknown decoder: {}", args.decoder));
+    let points = match sweep(
+        decoder.as_ref(),
+        &SweepConfig {
+            distances: args.distances,
+            e_ds: args.e_d,
+            e_m: args.e_m,
+            e_m_ratio: args.e_m_ratio,
+            c: args.c,
+            seed: args.seed,
+            max_shots: args.shots,
+            target: args.target,
+            z: args.z,
+        },
+    ) {
+        Ok(points) => points,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+    let csv = to_csv(&points, args.z);
+    match &args.csv {
+        Some(path) => {
+            std::fs::write(path, csv).unwrap_or_else(|_| panic!("failed to write {}", path))
+        }
+        None => print!("{}", csv),
+    }
+    if let Some(path) = &args.svg {
+        svg::save(path, &plot(&points, args.z, &theme))
+            .unwrap_or_else(|_| panic!("failed to write {}", path));
+    }
+    for crossing in crossings(&points) {
+        eprintln!(
+            "d = {} / {}: threshold near e_d = {:.5}",
+            crossing.d1, crossing.d2, crossing.e_d
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::input::Input;
+use crate::output::Output;
+use crate::syndrome::Syndromes;
+
+pub mod blossom;
+pub mod mwpm;
+pub mod union_find;
+
+// Corrections of one case and one Pauli type, per cycle.
+pub type Corrections = Vec<Vec<(usize, usize)>>;
+
+pub trait Decoder {
+    // Name used to select the decoder in the registry.
+    fn name(&self) -> &'static str;
+
+    // Decodes one case from its observed syndromes and returns the X and Z
+    // corrections of every cycle.
+    fn decode(&self, syndromes: &Syndromes) -> (Corrections, Corrections);
+}
+
+// The qubits set in flip[cycle][i][j], by cycle in row-major order.
+fn flipped(flip: &[Vec<Vec<bool>>]) -> Corrections {
+    flip.iter()
+        .map(|grid| {
+            grid.iter()
+                .enumerate()
+                .flat_map(|(i, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|&(_, &set)| set)
+                        .map(move |(j, _)| (i, j))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// All decoders built into the crate.
+pub fn decoders() -> Vec<Box<dyn Decoder>> {
+    vec![Box::new(mwpm::Mwpm), Box::new(union_find::UnionFind)]
+}
+
+pub fn find(name: &str) -> Option<Box<dyn Decoder>> {
+    decoders()
+        .into_iter()
+        .find(|decoder| decoder.name() == name)
+}
+
+// Decodes every case of the input.
+pub fn run(decoder: &dyn Decoder, input: &Input) -> Output {
+    let mut x_correction = vec![];
+    let mut z_correction = vec![];
+    for case in 0..input.t {
+        let (x, z) = decoder.decode(&Syndromes::observe(input, case));
+        x_correction.push(x);
+        z_correction.push(z);
+    }
+    Output {
+        x_correction,
+        z_correction,
+    }
+}
@@ -0,0 +1,286 @@
+// Minimum-weight perfect matching decoder.
+//
+// Every detection event is either matched to another event or sent to the
+// boundary at the cost of its distance to the boundary. The events and a
+// mirror copy of them are matched perfectly, an event matched to its own
+// mirror going to the boundary; the mirror half repeats the choice of the
+// event half, so an optimal perfect matching costs twice an optimal choice.
+// The matched paths are applied as corrections in the cycle of each
+// data-error edge.
+//
+// This is an approximation of minimum-weight matching: a bounded Dijkstra
+// search joins each event only to its NEIGHBOURS nearest events, so a
+// matching that pairs an event with one further away is never considered.
+// A pair is also left out when sending both of its events to the boundary
+// is cheaper than joining them, which never loses the optimum.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use super::blossom::min_weight_perfect_matching;
+use super::{flipped, Corrections, Decoder};
+use crate::graph::{DecodingGraph, Pauli};
+use crate::syndrome::Syndromes;
+
+// number of nearest events each event is connected to; pairs further apart
+// are never matched
+const NEIGHBOURS: usize = 8;
+
+pub struct Mwpm;
+
+impl Decoder for Mwpm {
+    fn name(&self) -> &'static str {
+        "mwpm"
+    }
+
+    fn decode(&self, syndromes: &Syndromes) -> (Corrections, Corrections) {
+        let x_graph = DecodingGraph::for_syndromes(syndromes, Pauli::X);
+        let z_graph = DecodingGraph::for_syndromes(syndromes, Pauli::Z);
+        (
+            decode_graph(&x_graph, &x_graph.detection_events(syndromes)),
+            decode_graph(&z_graph, &z_graph.detection_events(syndromes)),
+        )
+    }
+}
+
+// Matches the given detection events and returns the corrections of each cycle.
+pub fn decode_graph(graph: &DecodingGraph, events: &[usize]) -> Corrections {
+    let mut search = Dijkstra::new(graph);
+    let mut flip = vec![vec![vec![false; graph.d]; graph.d]; graph.c];
+    for (u, v) in match_events(graph, &mut search, events) {
+        for e in search.path(u, v) {
+            if let Some((x, y)) = graph.edges[e].qubit {
+                flip[graph.edges[e].cycle][x][y] ^= true;
+            }
+        }
+    }
+
+    flipped(&flip)
+}
+
+// Pairs of matched nodes; None stands for the boundary. Events that can
+// neither reach the boundary nor be paired up are left out.
+fn match_events(
+    graph: &DecodingGraph,
+    search: &mut Dijkstra,
+    events: &[usize],
+) -> Vec<(usize, Option<usize>)> {
+    let n = events.len();
+    let mut event_index = vec![None; graph.num_nodes()];
+    for (k, &e) in events.iter().enumerate() {
+        event_index[e] = Some(k);
+    }
+
+    // Distance of each event to the boundary. An event that cannot reach it
+    // is charged more than all the other paths together, so as many of them
+    // as possible are paired up.
+    let to_boundary = search.boundary_distances();
+    let horizon = to_boundary.iter().flatten().copied().max().unwrap_or(0);
+    let boundary: Vec<Option<i64>> = events.iter().map(|&e| to_boundary[e]).collect();
+
+    // candidate edges between events; an event further away than its own
+    // boundary distance plus the largest one is never worth matching
+    let mut pairs = HashSet::new();
+    let mut candidates = vec![];
+    for (k, &e) in events.iter().enumerate() {
+        let radius = boundary[k].map_or(i64::MAX, |b| b + horizon);
+        for (node, dist) in search.nearest(e, &event_index, NEIGHBOURS, radius) {
+            let l = event_index[node].unwrap();
+            if pairs.insert((k.min(l), k.max(l))) {
+                candidates.push((k.min(l), k.max(l), dist));
+            }
+        }
+    }
+    let unreachable =
+        candidates.iter().map(|e| e.2).sum::<i64>() + boundary.iter().flatten().sum::<i64>() + 1;
+    let penalty = |k: usize| boundary[k].unwrap_or(unreachable);
+    candidates.retain(|&(k, l, dist)| dist < penalty(k) + penalty(l));
+
+    // split into connected components and match each one separately
+    let mut uf: Vec<usize> = (0..n).collect();
+    fn find(uf: &mut [usize], x: usize) -> usize {
+        if uf[x] != x {
+            uf[x] = find(uf, uf[x]);
+        }
+        uf[x]
+    }
+    for &(k, l, _) in &candidates {
+        let (a, b) = (find(&mut uf, k), find(&mut uf, l));
+        uf[a] = b;
+    }
+    let mut members = vec![vec![]; n];
+    for k in 0..n {
+        let root = find(&mut uf, k);
+        members[root].push(k);
+    }
+    let mut local = vec![0; n];
+    let mut component_edges = vec![vec![]; n];
+    for &(k, l, dist) in &candidates {
+        component_edges[find(&mut uf, k)].push((k, l, dist));
+    }
+
+    let mut matched = vec![];
+    for root in 0..n {
+        let member = &members[root];
+        if member.is_empty() {
+            continue;
+        }
+        for (idx, &k) in member.iter().enumerate() {
+            local[k] = idx;
+        }
+        // local[k] + m is the mirror of event k
+        let m = member.len();
+        let mut edges = vec![];
+        for &k in member {
+            edges.push((local[k], local[k] + m, 2 * penalty(k)));
+        }
+        for &(k, l, dist) in &component_edges[root] {
+            edges.push((local[k], local[l], dist));
+            edges.push((local[k] + m, local[l] + m, dist));
+        }
+        let mate = min_weight_perfect_matching(2 * m, &edges)
+            .expect("every event can match its own mirror, so a perfect matching exists");
+        for (idx, &k) in member.iter().enumerate() {
+            let other = mate[idx];
+            if other >= m {
+                if boundary[k].is_some() {
+                    matched.push((events[k], None));
+                }
+            } else if idx < other {
+                matched.push((events[k], Some(events[member[other]])));
+            }
+        }
+    }
+    matched
+}
+
+struct Dijkstra<'a> {
+    graph: &'a DecodingGraph,
+    dist: Vec<i64>,
+    prev: Vec<usize>,
+    touched: Vec<usize>,
+    heap: BinaryHeap<Reverse<(i64, usize)>>,
+}
+
+impl<'a> Dijkstra<'a> {
+    fn new(graph: &'a DecodingGraph) -> Self {
+        // the extra node is the boundary
+        let n = graph.num_nodes() + 1;
+        Dijkstra {
+            graph,
+            dist: vec![i64::MAX; n],
+            prev: vec![usize::MAX; n],
+            touched: vec![],
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    fn reset(&mut self) {
+        for &v in &self.touched {
+            self.dist[v] = i64::MAX;
+            self.prev[v] = usize::MAX;
+        }
+        self.touched.clear();
+        self.heap.clear();
+    }
+
+    // Runs the search from the given (node, distance) sources; `visit` is
+    // called on every settled node and stops the search by returning false.
+    fn run(&mut self, sources: &[(usize, i64)], mut visit: impl FnMut(usize, i64) -> bool) {
+        self.reset();
+        let boundary = self.graph.num_nodes();
+        for &(source, dist) in sources {
+            if dist < self.dist[source] {
+                if self.dist[source] == i64::MAX {
+                    self.touched.push(source);
+                }
+                self.dist[source] = dist;
+                self.heap.push(Reverse((dist, source)));
+            }
+        }
+        while let Some(Reverse((dist, v))) = self.heap.pop() {
+            if dist > self.dist[v] {
+                continue;
+            }
+            if !visit(v, dist) {
+                return;
+            }
+            if v == boundary {
+                continue;
+            }
+            for &e in &self.graph.adjacency[v] {
+                let edge = &self.graph.edges[e];
+                let next = match edge.v {
+                    None => boundary,
+                    Some(w) if w == v => edge.u,
+                    Some(w) => w,
+                };
+                let nd = dist + edge.weight;
+                if nd < self.dist[next] {
+                    if self.dist[next] == i64::MAX {
+                        self.touched.push(next);
+                    }
+                    self.dist[next] = nd;
+                    self.prev[next] = e;
+                    self.heap.push(Reverse((nd, next)));
+                }
+            }
+        }
+    }
+
+    // Distance of every node to the boundary, None where it is unreachable.
+    fn boundary_distances(&mut self) -> Vec<Option<i64>> {
+        let sources: Vec<(usize, i64)> = self
+            .graph
+            .edges
+            .iter()
+            .filter(|edge| edge.v.is_none())
+            .map(|edge| (edge.u, edge.weight))
+            .collect();
+        let mut ret = vec![None; self.graph.num_nodes()];
+        self.run(&sources, |v, dist| {
+            if let Some(d) = ret.get_mut(v) {
+                *d = Some(dist);
+            }
+            true
+        });
+        ret
+    }
+
+    // Up to `limit` nearest events closer than `radius`, with their distances.
+    fn nearest(
+        &mut self,
+        source: usize,
+        event_index: &[Option<usize>],
+        limit: usize,
+        radius: i64,
+    ) -> Vec<(usize, i64)> {
+        let boundary = self.graph.num_nodes();
+        let mut found = vec![];
+        self.run(&[(source, 0)], |v, dist| {
+            if dist >= radius {
+                return false;
+            }
+            if v != source && v != boundary && event_index[v].is_some() {
+                found.push((v, dist));
+            }
+            found.len() < limit
+        });
+        found
+    }
+
+    // Edges of a shortest path between two nodes (or a node and the
+    // boundary), which the caller has to know to be connected.
+    fn path(&mut self, source: usize, target: Option<usize>) -> Vec<usize> {
+        let target = target.unwrap_or(self.graph.num_nodes());
+        self.run(&[(source, 0)], |v, _| v != target);
+        let mut edges = vec![];
+        let mut v = target;
+        while v != source {
+            let e = self.prev[v];
+            edges.push(e);
+            let edge = &self.graph.edges[e];
+            v = if edge.u == v { edge.v.unwrap() } else { edge.u };
+        }
+        edges
+    }
+}
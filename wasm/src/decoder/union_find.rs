@@ -0,0 +1,257 @@
+// Union-Find decoder (Delfosse-Nickerson) with weighted cluster growth.
+//
+// Every detection event starts an odd cluster. In each growth step all odd
+// clusters grow their frontier edges at the same speed until the next edge
+// is fully grown; fully grown edges merge the clusters they touch, and a
+// cluster that reaches the boundary becomes neutral. When no odd cluster is
+// left, a spanning forest of the grown edges is peeled from the leaves to
+// find the corrections.
+use std::collections::VecDeque;
+
+use super::{flipped, Corrections, Decoder};
+use crate::graph::{DecodingGraph, Edge, Pauli};
+use crate::syndrome::Syndromes;
+
+pub struct UnionFind;
+
+impl Decoder for UnionFind {
+    fn name(&self) -> &'static str {
+        "union-find"
+    }
+
+    fn decode(&self, syndromes: &Syndromes) -> (Corrections, Corrections) {
+        let x_graph = DecodingGraph::for_syndromes(syndromes, Pauli::X);
+        let z_graph = DecodingGraph::for_syndromes(syndromes, Pauli::Z);
+        (
+            decode_graph(&x_graph, &x_graph.detection_events(syndromes)),
+            decode_graph(&z_graph, &z_graph.detection_events(syndromes)),
+        )
+    }
+}
+
+pub fn decode_graph(graph: &DecodingGraph, events: &[usize]) -> Corrections {
+    decode_graph_traced(graph, events).0
+}
+
+// Also returns the growth steps: the edges that became fully grown in each step.
+pub fn decode_graph_traced(
+    graph: &DecodingGraph,
+    events: &[usize],
+) -> (Corrections, Vec<Vec<usize>>) {
+    let n = graph.num_nodes();
+    let boundary = n;
+    let mut clusters = Clusters::new(graph);
+    for &e in events {
+        clusters.parity[e] = 1;
+    }
+
+    let mut support = vec![0; graph.edges.len()];
+    let mut rate = vec![0; graph.edges.len()];
+    let mut steps = vec![];
+    let mut is_odd = vec![false; n];
+    loop {
+        let mut odd = vec![];
+        for &e in events {
+            let r = clusters.find(e);
+            if clusters.parity[r] == 1 && !clusters.neutral[r] && !is_odd[r] {
+                is_odd[r] = true;
+                odd.push(r);
+            }
+        }
+        if odd.is_empty() {
+            break;
+        }
+
+        // frontier edges, and how many odd clusters grow each of them; edges
+        // that became grown or internal since the last step are dropped
+        let mut frontier = vec![];
+        for &r in &odd {
+            let mut edges = std::mem::take(&mut clusters.frontier[r]);
+            edges.retain(|&e| {
+                let edge = &graph.edges[e];
+                support[e] < edge.weight
+                    && !(clusters.find(edge.u) == r
+                        && edge.v.is_some_and(|w| clusters.find(w) == r))
+            });
+            for &e in &edges {
+                if rate[e] == 0 {
+                    frontier.push(e);
+                }
+                rate[e] += 1;
+            }
+            clusters.frontier[r] = edges;
+        }
+        for &r in &odd {
+            is_odd[r] = false;
+        }
+
+        // Odd clusters without frontier edges can reach neither the boundary
+        // nor another cluster; peeling leaves one event of each unmatched.
+        let Some(delta) = frontier
+            .iter()
+            .map(|&e| (graph.edges[e].weight - support[e] + rate[e] - 1) / rate[e])
+            .min()
+        else {
+            break;
+        };
+        let mut grown = vec![];
+        for &e in &frontier {
+            support[e] = (support[e] + delta * rate[e]).min(graph.edges[e].weight);
+            rate[e] = 0;
+            if support[e] == graph.edges[e].weight {
+                grown.push(e);
+            }
+        }
+        for &e in &grown {
+            clusters.grow(&graph.edges[e]);
+        }
+        steps.push(grown);
+    }
+
+    // spanning forest of the grown edges, rooted at the boundary where possible
+    let mut forest = vec![vec![]; n + 1];
+    for (e, edge) in graph.edges.iter().enumerate() {
+        if support[e] == edge.weight {
+            let v = edge.v.unwrap_or(boundary);
+            forest[edge.u].push((v, e));
+            forest[v].push((edge.u, e));
+        }
+    }
+    let mut parent_edge = vec![usize::MAX; n + 1];
+    let mut visited = vec![false; n + 1];
+    let mut order = vec![];
+    for root in std::iter::once(boundary).chain(events.iter().copied()) {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for &(w, e) in &forest[v] {
+                if !visited[w] {
+                    visited[w] = true;
+                    parent_edge[w] = e;
+                    queue.push_back(w);
+                }
+            }
+        }
+    }
+
+    // peel from the leaves
+    let mut marked = vec![false; n + 1];
+    for &e in events {
+        marked[e] = true;
+    }
+    let mut flip = vec![vec![vec![false; graph.d]; graph.d]; graph.c];
+    for &v in order.iter().rev() {
+        if !marked[v] || parent_edge[v] == usize::MAX {
+            continue;
+        }
+        let edge = &graph.edges[parent_edge[v]];
+        let parent = if edge.u == v {
+            edge.v.unwrap_or(boundary)
+        } else {
+            edge.u
+        };
+        marked[v] = false;
+        marked[parent] ^= true;
+        if let Some((x, y)) = edge.qubit {
+            flip[edge.cycle][x][y] ^= true;
+        }
+    }
+
+    (flipped(&flip), steps)
+}
+
+// Connected components of the nodes after the first `step` growth steps.
+// Only components containing a detection event are returned.
+pub fn clusters_at(
+    graph: &DecodingGraph,
+    events: &[usize],
+    steps: &[Vec<usize>],
+    step: usize,
+) -> Vec<Vec<usize>> {
+    let n = graph.num_nodes();
+    let mut clusters = Clusters::new(graph);
+    for grown in steps.iter().take(step) {
+        for &e in grown {
+            clusters.grow(&graph.edges[e]);
+        }
+    }
+    let mut index = vec![usize::MAX; n];
+    let mut ret: Vec<Vec<usize>> = vec![];
+    for &e in events {
+        let r = clusters.find(e);
+        if index[r] == usize::MAX {
+            index[r] = ret.len();
+            ret.push(clusters.members[r].clone());
+        }
+    }
+    ret
+}
+
+struct Clusters {
+    parent: Vec<usize>,
+    members: Vec<Vec<usize>>,
+    parity: Vec<u8>,
+    // touches the boundary
+    neutral: Vec<bool>,
+    // edges leaving the cluster, possibly with stale internal or grown ones
+    frontier: Vec<Vec<usize>>,
+}
+
+impl Clusters {
+    fn new(graph: &DecodingGraph) -> Clusters {
+        let n = graph.num_nodes();
+        Clusters {
+            parent: (0..n).collect(),
+            members: (0..n).map(|v| vec![v]).collect(),
+            parity: vec![0; n],
+            neutral: vec![false; n],
+            frontier: graph.adjacency.clone(),
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    // Merges the clusters joined by a fully grown edge.
+    fn grow(&mut self, edge: &Edge) {
+        match edge.v {
+            Some(v) => self.union(edge.u, v),
+            None => {
+                let r = self.find(edge.u);
+                self.neutral[r] = true;
+            }
+        }
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.members[a].len() < self.members[b].len() {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        let moved = std::mem::take(&mut self.members[b]);
+        self.members[a].extend(moved);
+        let moved = std::mem::take(&mut self.frontier[b]);
+        self.frontier[a].extend(moved);
+        self.parity[a] ^= self.parity[b];
+        self.neutral[a] |= self.neutral[b];
+    }
+}
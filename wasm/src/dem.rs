@@ -0,0 +1,176 @@
+// Detector error model of an instance.
+//
+// Detector `cycle * n + k` is the k-th ancilla in row-major order (of n) in
+// that cycle. Observable L0 is flipped by X errors on column 0 and L1 by Z
+// errors on row 0. Each data qubit has independent X, Y and Z mechanisms at
+// the cycle's `data_error_rate`; Y is written decomposed into its X and Z
+// parts. A measurement error flips the ancilla's detectors in its own cycle
+// and the next one. The detectors are those of both `to_stim` circuits; L0
+// is the observable of memory Z, L1 that of memory X.
+use std::fmt::Write;
+
+use crate::graph::data_error_rate;
+use crate::input::Input;
+use crate::sdk::{Lattice, Site};
+
+#[derive(Clone, Debug)]
+pub struct Mechanism {
+    pub probability: f64,
+    // one (detectors, observables) part per Pauli component; a Y error has two
+    pub parts: Vec<(Vec<usize>, Vec<usize>)>,
+}
+
+impl Mechanism {
+    pub fn detectors(&self) -> Vec<usize> {
+        let mut ret: Vec<usize> = self.parts.iter().flat_map(|p| p.0.clone()).collect();
+        ret.sort();
+        ret
+    }
+
+    pub fn observables(&self) -> Vec<usize> {
+        let mut ret: Vec<usize> = self.parts.iter().flat_map(|p| p.1.clone()).collect();
+        ret.sort();
+        ret
+    }
+}
+
+// Detector coordinates (i, j, cycle) in detector order.
+pub fn detectors(input: &Input) -> Vec<(usize, usize, usize)> {
+    let mut ret = vec![];
+    for cycle in 0..input.c {
+        for i in 0..input.d {
+            for j in 0..input.d {
+                if (i + j) % 2 == 1 {
+                    ret.push((i, j, cycle));
+                }
+            }
+        }
+    }
+    ret
+}
+
+pub fn mechanisms(input: &Input) -> Vec<Mechanism> {
+    let d = input.d;
+    let lattice = Lattice::of(input);
+    let mut index = vec![vec![0; d]; d];
+    let mut n = 0;
+    for site in lattice.sites() {
+        if !matches!(site, Site::Data(_)) {
+            let (i, j) = site.into();
+            index[i][j] = n;
+            n += 1;
+        }
+    }
+
+    let mut ret = vec![];
+    for cycle in 0..input.c {
+        // DEPOLARIZE1(e_d) is the same as independent X, Y and Z flips with
+        // this probability each.
+        let e_d = data_error_rate(input.e_d, input.c, cycle);
+        let p_data = (1.0 - (1.0 - 4.0 * e_d / 3.0).sqrt()) / 2.0;
+        if e_d > 0.0 {
+            for q in lattice.data_qubits() {
+                let detector = |(i, j): (usize, usize)| cycle * n + index[i][j];
+                let mut x_detectors: Vec<usize> = lattice
+                    .x_neighbours(q)
+                    .map(|a| detector(a.into()))
+                    .collect();
+                let mut z_detectors: Vec<usize> = lattice
+                    .z_neighbours(q)
+                    .map(|a| detector(a.into()))
+                    .collect();
+                x_detectors.sort();
+                z_detectors.sort();
+                let x_part = (x_detectors, if q.j() == 0 { vec![0] } else { vec![] });
+                let z_part = (z_detectors, if q.i() == 0 { vec![1] } else { vec![] });
+                for parts in [
+                    vec![x_part.clone()],
+                    vec![x_part.clone(), z_part.clone()],
+                    vec![z_part.clone()],
+                ] {
+                    ret.push(Mechanism {
+                        probability: p_data,
+                        parts,
+                    });
+                }
+            }
+        }
+        if input.e_m > 0.0 {
+            for k in 0..n {
+                let mut detectors = vec![cycle * n + k];
+                if cycle + 1 < input.c {
+                    detectors.push((cycle + 1) * n + k);
+                }
+                ret.push(Mechanism {
+                    probability: input.e_m,
+                    parts: vec![(detectors, vec![])],
+                });
+            }
+        }
+    }
+    ret
+}
+
+// The model in Stim's DEM text format.
+pub fn to_dem(input: &Input) -> String {
+    let mut s = String::new();
+    for mechanism in mechanisms(input) {
+        let parts: Vec<String> = mechanism
+            .parts
+            .iter()
+            .map(|(detectors, observables)| {
+                detectors
+                    .iter()
+                    .map(|k| format!("D{}", k))
+                    .chain(observables.iter().map(|k| format!("L{}", k)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        writeln!(s, "error({}) {}", mechanism.probability, parts.join(" ^ ")).unwrap();
+    }
+    for (k, (i, j, cycle)) in detectors(input).into_iter().enumerate() {
+        writeln!(s, "detector({}, {}, {}) D{}", i, j, cycle, k).unwrap();
+    }
+    writeln!(s, "logical_observable L0").unwrap();
+    writeln!(s, "logical_observable L1").unwrap();
+    s
+}
+
+// Sparse 0/1 matrix with one column per mechanism, in Matrix Market format.
+fn coordinate_matrix(rows: usize, columns: &[Vec<usize>]) -> String {
+    let nnz: usize = columns.iter().map(|column| column.len()).sum();
+    let mut s = String::new();
+    writeln!(s, "%%MatrixMarket matrix coordinate integer general").unwrap();
+    writeln!(s, "{} {} {}", rows, columns.len(), nnz).unwrap();
+    for (col, column) in columns.iter().enumerate() {
+        for row in column {
+            writeln!(s, "{} {} 1", row + 1, col + 1).unwrap();
+        }
+    }
+    s
+}
+
+// Space-time parity-check matrix: detectors x mechanisms.
+pub fn check_matrix(input: &Input) -> String {
+    let columns: Vec<Vec<usize>> = mechanisms(input).iter().map(|m| m.detectors()).collect();
+    coordinate_matrix(detectors(input).len(), &columns)
+}
+
+// Logical observable matrix: observables x mechanisms.
+pub fn observable_matrix(input: &Input) -> String {
+    let columns: Vec<Vec<usize>> = mechanisms(input).iter().map(|m| m.observables()).collect();
+    coordinate_matrix(2, &columns)
+}
+
+// Probability of every mechanism, as a dense column vector.
+pub fn priors(input: &Input) -> String {
+    let mechanisms = mechanisms(input);
+    let mut s = String::new();
+    writeln!(s, "%%MatrixMarket matrix array real general").unwrap();
+    writeln!(s, "{} 1", mechanisms.len()).unwrap();
+    for mechanism in mechanisms {
+        writeln!(s, "{}", mechanism.probability).unwrap();
+    }
+    s
+}
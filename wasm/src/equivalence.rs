@@ -0,0 +1,66 @@
+// Whether two outputs for the same input correct each case the same way.
+//
+// The residual frames of the two outputs differ by the XOR of their
+// corrections. If that difference has a syndrome, the outputs leave different
+// ancillas faulty. Otherwise it is a product of stabilizers and possibly
+// logical operators, and `hakidashi` sweeps it down to site (0, 0), whose
+// X and Z bits tell the logical operators apart from the stabilizers.
+use crate::input::Input;
+use crate::output::Output;
+use crate::score::residual;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    // same stabilizer coset: the outputs score the same on this case
+    Equivalent,
+    // same syndrome, but the residuals differ by the X and / or Z logical
+    Logical { x: bool, z: bool },
+    // the residual syndromes differ at this many ancillas
+    Syndrome(usize),
+}
+
+impl std::fmt::Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Relation::Equivalent => write!(f, "equivalent"),
+            Relation::Logical { x, z } => {
+                let which = match (x, z) {
+                    (true, true) => "X and Z",
+                    (true, false) => "X",
+                    _ => "Z",
+                };
+                write!(f, "differ by the {} logical", which)
+            }
+            Relation::Syndrome(count) => write!(
+                f,
+                "syndromes differ at {} ancilla{}",
+                count,
+                if *count == 1 { "" } else { "s" }
+            ),
+        }
+    }
+}
+
+pub fn compare_case(input: &Input, a: &Output, b: &Output, case: usize) -> Relation {
+    let mut difference = residual(input, a, case);
+    difference.xor(&residual(input, b, case));
+    let faulty = difference.faulty();
+    if faulty > 0 {
+        return Relation::Syndrome(faulty);
+    }
+    difference.hakidashi();
+    let x = difference.x.get(0, 0);
+    let z = difference.z.get(0, 0);
+    if x || z {
+        Relation::Logical { x, z }
+    } else {
+        Relation::Equivalent
+    }
+}
+
+// The relation of every case, in order.
+pub fn compare(input: &Input, a: &Output, b: &Output) -> Vec<Relation> {
+    (0..input.t)
+        .map(|case| compare_case(input, a, b, case))
+        .collect()
+}
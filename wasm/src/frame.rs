@@ -0,0 +1,242 @@
+// Bit-packed Pauli frame of the d x d lattice.
+//
+// Row i of a BitGrid is `stride` 64-bit words with bit j holding site (i, j).
+// Every ancilla's neighbours are data qubits, so the syndrome of a whole row
+// is the XOR of the row shifted left and right and the rows above and below,
+// masked to the ancillas of that row: at even i these are the odd-j ancillas
+// that detect X errors, at odd i the even-j ancillas that detect Z errors.
+
+// bits at even / odd positions of a word
+const EVEN: u64 = 0x5555_5555_5555_5555;
+const ODD: u64 = 0xAAAA_AAAA_AAAA_AAAA;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitGrid {
+    d: usize,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(d: usize) -> BitGrid {
+        let stride = d.div_ceil(64);
+        BitGrid {
+            d,
+            stride,
+            words: vec![0; d * stride],
+        }
+    }
+
+    pub fn from_grid(grid: &[Vec<usize>]) -> BitGrid {
+        let mut ret = BitGrid::new(grid.len());
+        for (i, row) in grid.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                if v & 1 == 1 {
+                    ret.flip(i, j);
+                }
+            }
+        }
+        ret
+    }
+
+    pub fn to_grid(&self) -> Vec<Vec<usize>> {
+        (0..self.d)
+            .map(|i| (0..self.d).map(|j| self.get(i, j) as usize).collect())
+            .collect()
+    }
+
+    pub fn d(&self) -> usize {
+        self.d
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        self.words[i * self.stride + j / 64] >> (j % 64) & 1 == 1
+    }
+
+    pub fn flip(&mut self, i: usize, j: usize) {
+        self.words[i * self.stride + j / 64] ^= 1 << (j % 64);
+    }
+
+    // XORs a list of sites into the grid.
+    pub fn apply(&mut self, sites: &[(usize, usize)]) {
+        for &(i, j) in sites {
+            self.flip(i, j);
+        }
+    }
+
+    pub fn xor(&mut self, other: &BitGrid) {
+        assert_eq!(self.d, other.d);
+        for (w, o) in self.words.iter_mut().zip(&other.words) {
+            *w ^= o;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // Set sites in row-major order.
+    pub fn ones(&self) -> Vec<(usize, usize)> {
+        let mut ret = vec![];
+        for i in 0..self.d {
+            for (k, &word) in self.row(i).iter().enumerate() {
+                let mut word = word;
+                while word != 0 {
+                    ret.push((i, k * 64 + word.trailing_zeros() as usize));
+                    word &= word - 1;
+                }
+            }
+        }
+        ret
+    }
+
+    fn row(&self, i: usize) -> &[u64] {
+        &self.words[i * self.stride..(i + 1) * self.stride]
+    }
+
+    fn row_mut(&mut self, i: usize) -> &mut [u64] {
+        &mut self.words[i * self.stride..(i + 1) * self.stride]
+    }
+
+    // `pattern` repeated over a row, cut off at column d
+    fn mask(&self, pattern: u64) -> Vec<u64> {
+        (0..self.stride)
+            .map(|k| {
+                let width = self.d - k * 64;
+                if width >= 64 {
+                    pattern
+                } else {
+                    pattern & ((1 << width) - 1)
+                }
+            })
+            .collect()
+    }
+
+    // bit j of the result is bit j - 1 + bit j + 1 of the row, masked
+    fn horizontal(&self, row: &[u64], mask: &[u64]) -> Vec<u64> {
+        (0..self.stride)
+            .map(|k| {
+                let left = row[k] << 1 | if k > 0 { row[k - 1] >> 63 } else { 0 };
+                let right = row[k] >> 1
+                    | if k + 1 < self.stride {
+                        row[k + 1] << 63
+                    } else {
+                        0
+                    };
+                (left ^ right) & mask[k]
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub x: BitGrid,
+    pub z: BitGrid,
+}
+
+impl Frame {
+    pub fn new(d: usize) -> Frame {
+        Frame {
+            x: BitGrid::new(d),
+            z: BitGrid::new(d),
+        }
+    }
+
+    pub fn xor(&mut self, other: &Frame) {
+        self.x.xor(&other.x);
+        self.z.xor(&other.z);
+    }
+
+    // Measured value of every ancilla without measurement errors: X-error
+    // ancillas at odd j, Z-error ancillas at even j.
+    pub fn syndrome(&self) -> BitGrid {
+        let d = self.x.d;
+        let mut ret = BitGrid::new(d);
+        let masks = [self.x.mask(ODD), self.x.mask(EVEN)];
+        for i in 0..d {
+            let (errors, mask) = if i % 2 == 0 {
+                (&self.x, &masks[0])
+            } else {
+                (&self.z, &masks[1])
+            };
+            let mut row = errors.horizontal(errors.row(i), mask);
+            for k in 0..errors.stride {
+                let mut vertical = 0;
+                if i > 0 {
+                    vertical ^= errors.row(i - 1)[k];
+                }
+                if i + 1 < d {
+                    vertical ^= errors.row(i + 1)[k];
+                }
+                row[k] ^= vertical & mask[k];
+            }
+            ret.row_mut(i).copy_from_slice(&row);
+        }
+        ret
+    }
+
+    // Number of ancillas that detect a remaining error.
+    pub fn faulty(&self) -> usize {
+        self.syndrome().count_ones()
+    }
+
+    // Whether the X and Z logical operators are flipped: the parity of X
+    // errors on column 0 and of Z errors on row 0, which is what `hakidashi`
+    // leaves at (0, 0).
+    pub fn logical(&self) -> (bool, bool) {
+        let mut x = false;
+        for i in (0..self.x.d).step_by(2) {
+            x ^= self.x.get(i, 0);
+        }
+        let mask = self.z.mask(EVEN);
+        let z = self
+            .z
+            .row(0)
+            .iter()
+            .zip(&mask)
+            .map(|(w, m)| (w & m).count_ones())
+            .sum::<u32>()
+            % 2
+            == 1;
+        (x, z)
+    }
+
+    // Multiplies stabilizers into the frame like `hakidashi` (for odd d): X
+    // errors on the even rows are swept up to row 0 a whole row at a time, Z
+    // errors on the even columns left to column 0 one column at a time.
+    pub fn hakidashi(&mut self) {
+        let d = self.x.d;
+        let even = self.x.mask(EVEN);
+        for i in (1..d.saturating_sub(1)).rev().step_by(2) {
+            let m: Vec<u64> = self
+                .x
+                .row(i + 1)
+                .iter()
+                .zip(&even)
+                .map(|(w, e)| w & e)
+                .collect();
+            let all = self.x.mask(!0);
+            let side = self.x.horizontal(&m, &all);
+            for k in 0..self.x.stride {
+                self.x.row_mut(i + 1)[k] ^= m[k];
+                self.x.row_mut(i)[k] ^= side[k];
+                self.x.row_mut(i - 1)[k] ^= m[k];
+            }
+        }
+        for j in (1..d.saturating_sub(1)).rev().step_by(2) {
+            for i in (0..d).step_by(2) {
+                if self.z.get(i, j + 1) {
+                    self.z.flip(i, j + 1);
+                    if i != 0 {
+                        self.z.flip(i - 1, j);
+                    }
+                    if i != d - 1 {
+                        self.z.flip(i + 1, j);
+                    }
+                    self.z.flip(i, j - 1);
+                }
+            }
+        }
+    }
+}
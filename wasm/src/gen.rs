@@ -0,0 +1,108 @@
+use rand::prelude::*;
+
+use crate::input::{Input, Instance};
+
+// No data errors occur in the last QUIET_CYCLES cycles of a case.
+pub const QUIET_CYCLES: usize = 5;
+
+#[allow(
+    unused_assignments,
+    unused_variables,
+    clippy::manual_is_multiple_of,
+    clippy::needless_return,
+    clippy::redundant_field_names
+)]
+pub fn gen(seed: u64) -> Input {
+    let c = 100;
+    let d = 21;
+    let mut t = 0;
+    let mut e_d = 0.;
+    let mut e_m = 0.;
+
+    if seed > 100000 {
+        t = 100;
+    } else {
+        t = 1;
+    }
+
+    if ((seed / 200) as usize) % 5 == 0 {
+        e_d = 0.001;
+        e_m = 0.01;
+    } else if ((seed / 200) as usize) % 5 == 1 {
+        e_d = 0.01;
+        e_m = 0.03;
+    } else if ((seed / 200) as usize) % 5 == 2 {
+        e_d = 0.03;
+        e_m = 0.00;
+    } else if ((seed / 200) as usize) % 5 == 3 {
+        e_d = 0.03;
+        e_m = 0.05;
+    } else if ((seed / 200) as usize) % 5 == 4 {
+        e_d = 0.05;
+        e_m = 0.1;
+    }
+
+    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(seed);
+    let mut testcase: Vec<Instance> = vec![];
+    for case in 0..t {
+        testcase.push(gen_instance(&mut rng, d, c, e_d, e_m));
+    }
+    return Input {
+        d: d,
+        c: c,
+        e_d: e_d,
+        e_m: e_m,
+        t: t,
+        testcase: testcase,
+    };
+}
+
+// Draws the errors of one case.
+#[allow(unused_parens, clippy::collapsible_if)]
+pub fn gen_instance<R: Rng>(rng: &mut R, d: usize, c: usize, e_d: f64, e_m: f64) -> Instance {
+    let mut x_depolarizing_total = vec![];
+    let mut z_depolarizing_total = vec![];
+    let mut measure_total = vec![];
+    for turn in 0..c {
+        let mut x_depolarizing_error = vec![];
+        let mut z_depolarizing_error = vec![];
+        if (turn < c.saturating_sub(QUIET_CYCLES)) {
+            for i in 0..d {
+                for j in 0..d {
+                    if ((i + j) % 2 == 0) {
+                        // with probability E_d / 3 X error occurs, with probability E_d / 3 Z error occurs, and with probability E_d / 3 Y error occurs.
+                        if rng.gen_bool(e_d) {
+                            let choice = rng.gen_range(0, 3);
+                            if choice == 0 {
+                                x_depolarizing_error.push((i, j));
+                            } else if choice == 1 {
+                                z_depolarizing_error.push((i, j));
+                            } else {
+                                x_depolarizing_error.push((i, j));
+                                z_depolarizing_error.push((i, j));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let mut measure_error = vec![];
+        for i in 0..d {
+            for j in 0..d {
+                if ((i + j) % 2 == 1) {
+                    if rng.gen_bool(e_m) {
+                        measure_error.push((i, j));
+                    }
+                }
+            }
+        }
+        x_depolarizing_total.push(x_depolarizing_error);
+        z_depolarizing_total.push(z_depolarizing_error);
+        measure_total.push(measure_error);
+    }
+    Instance {
+        x_depolarizing_error: x_depolarizing_total,
+        z_depolarizing_error: z_depolarizing_total,
+        measure_error: measure_total,
+    }
+}
@@ -0,0 +1,216 @@
+// Space-time detection-event graph used by the decoders.
+//
+// A node is an ancilla in one measurement round. A detection event at
+// round k is a change of the measured syndrome between rounds k-1 and k.
+// Data errors in cycle k connect the (at most two) ancillas around the data
+// qubit in round k; measurement errors in round k connect rounds k and k+1
+// of the same ancilla. Edges with a single endpoint go to the boundary; for
+// the measurement errors of the last round that is the time boundary, since
+// the final frame is scored without being measured again.
+//
+// The generator keeps the last QUIET_CYCLES cycles free of data errors, but
+// an input does not say whether it came from the generator. Data edges in
+// those cycles get the probability of the others scaled by QUIET_SCALE, far
+// below any measurement error, so they only explain events that nothing
+// else can. The Stim circuit and DEM use the same rates.
+use crate::gen::QUIET_CYCLES;
+use crate::input::Input;
+use crate::sdk::Lattice;
+use crate::syndrome::Syndromes;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pauli {
+    X,
+    Z,
+}
+
+impl Pauli {
+    // X ancillas sit at odd j and detect X errors, Z ancillas at even j.
+    pub fn is_ancilla(self, i: usize, j: usize) -> bool {
+        (i + j) % 2 == 1 && (j % 2 == 1) == (self == Pauli::X)
+    }
+}
+
+pub const WEIGHT_SCALE: f64 = 1000.0;
+
+pub const QUIET_SCALE: f64 = 1e-6;
+
+// Depolarizing probability of the data qubits in a cycle of c.
+pub fn data_error_rate(e_d: f64, c: usize, cycle: usize) -> f64 {
+    if cycle + QUIET_CYCLES < c {
+        e_d
+    } else {
+        e_d * QUIET_SCALE
+    }
+}
+
+// Log-likelihood weight of an independent flip with probability p.
+pub fn weight(p: f64) -> Option<i64> {
+    if p <= 0.0 {
+        return None;
+    }
+    Some(((((1.0 - p) / p).ln() * WEIGHT_SCALE).round() as i64).max(1))
+}
+
+#[derive(Clone, Debug)]
+pub struct Edge {
+    pub u: usize,
+    // None for boundary edges
+    pub v: Option<usize>,
+    pub weight: i64,
+    // data qubit flipped by this edge, None for measurement errors
+    pub qubit: Option<(usize, usize)>,
+    pub cycle: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct DecodingGraph {
+    pub pauli: Pauli,
+    pub d: usize,
+    pub c: usize,
+    pub ancillas: Vec<(usize, usize)>,
+    pub index: Vec<Vec<Option<usize>>>,
+    pub edges: Vec<Edge>,
+    pub adjacency: Vec<Vec<usize>>,
+}
+
+impl DecodingGraph {
+    pub fn new(input: &Input, pauli: Pauli) -> DecodingGraph {
+        DecodingGraph::build(input.d, input.c, input.e_d, input.e_m, pauli)
+    }
+
+    pub fn for_syndromes(syndromes: &Syndromes, pauli: Pauli) -> DecodingGraph {
+        DecodingGraph::build(
+            syndromes.d,
+            syndromes.c,
+            syndromes.e_d,
+            syndromes.e_m,
+            pauli,
+        )
+    }
+
+    fn build(d: usize, c: usize, e_d: f64, e_m: f64, pauli: Pauli) -> DecodingGraph {
+        let mut ancillas = vec![];
+        let mut index = vec![vec![None; d]; d];
+        for (i, row) in index.iter_mut().enumerate() {
+            for (j, slot) in row.iter_mut().enumerate() {
+                if pauli.is_ancilla(i, j) {
+                    *slot = Some(ancillas.len());
+                    ancillas.push((i, j));
+                }
+            }
+        }
+        let mut graph = DecodingGraph {
+            pauli,
+            d,
+            c,
+            ancillas,
+            index,
+            edges: vec![],
+            adjacency: vec![],
+        };
+        graph.adjacency = vec![vec![]; graph.num_nodes()];
+
+        let measure_weight = weight(e_m);
+        for cycle in 0..c {
+            // X errors occur with probability 2 e_d / 3 (X or Y), likewise Z errors.
+            if let Some(w) = weight(data_error_rate(e_d, c, cycle) * 2.0 / 3.0) {
+                for i in 0..d {
+                    for j in 0..d {
+                        if (i + j) % 2 != 0 {
+                            continue;
+                        }
+                        let nodes: Vec<usize> = graph
+                            .neighbours(i, j)
+                            .into_iter()
+                            .map(|a| graph.node(cycle, a))
+                            .collect();
+                        match nodes.len() {
+                            1 => graph.add_edge(nodes[0], None, w, Some((i, j)), cycle),
+                            2 => graph.add_edge(nodes[0], Some(nodes[1]), w, Some((i, j)), cycle),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            if let Some(w) = measure_weight {
+                for a in 0..graph.ancillas.len() {
+                    let next = if cycle + 1 < c {
+                        Some(graph.node(cycle + 1, a))
+                    } else {
+                        None
+                    };
+                    graph.add_edge(graph.node(cycle, a), next, w, None, cycle);
+                }
+            }
+        }
+        graph
+    }
+
+    fn add_edge(
+        &mut self,
+        u: usize,
+        v: Option<usize>,
+        weight: i64,
+        qubit: Option<(usize, usize)>,
+        cycle: usize,
+    ) {
+        let id = self.edges.len();
+        self.adjacency[u].push(id);
+        if let Some(v) = v {
+            self.adjacency[v].push(id);
+        }
+        self.edges.push(Edge {
+            u,
+            v,
+            weight,
+            qubit,
+            cycle,
+        });
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.c * self.ancillas.len()
+    }
+
+    pub fn node(&self, cycle: usize, ancilla: usize) -> usize {
+        cycle * self.ancillas.len() + ancilla
+    }
+
+    // (cycle, ancilla coordinate) of a node
+    pub fn position(&self, node: usize) -> (usize, (usize, usize)) {
+        (
+            node / self.ancillas.len(),
+            self.ancillas[node % self.ancillas.len()],
+        )
+    }
+
+    // Indices of the ancillas of this graph's type adjacent to data qubit (i, j).
+    pub fn neighbours(&self, i: usize, j: usize) -> Vec<usize> {
+        Lattice::new(self.d)
+            .neighbours(i, j)
+            .filter_map(|site| {
+                let (i, j) = site.into();
+                self.index[i][j]
+            })
+            .collect()
+    }
+
+    // Detection events of one case, as node indices in increasing order.
+    pub fn detection_events(&self, syndromes: &Syndromes) -> Vec<usize> {
+        let mut events = vec![];
+        for cycle in 0..self.c {
+            for (a, &(i, j)) in self.ancillas.iter().enumerate() {
+                let previous = if cycle == 0 {
+                    0
+                } else {
+                    syndromes.measured[cycle - 1][i][j]
+                };
+                if syndromes.measured[cycle][i][j] != previous {
+                    events.push(self.node(cycle, a));
+                }
+            }
+        }
+        events
+    }
+}
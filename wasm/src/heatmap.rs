@@ -0,0 +1,70 @@
+// How often each site of the lattice was hit over all cases and cycles of an
+// input, the data behind `vis::vis_heatmap`: data errors and corrections of
+// either type at the data qubits, measurement errors at the ancillas.
+use crate::input::Input;
+use crate::output::Output;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    XErrors,
+    ZErrors,
+    MeasurementErrors,
+    XCorrections,
+    ZCorrections,
+}
+
+impl Aggregate {
+    pub const ALL: [Aggregate; 5] = [
+        Aggregate::XErrors,
+        Aggregate::ZErrors,
+        Aggregate::MeasurementErrors,
+        Aggregate::XCorrections,
+        Aggregate::ZCorrections,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Aggregate::XErrors => "x-errors",
+            Aggregate::ZErrors => "z-errors",
+            Aggregate::MeasurementErrors => "measurement-errors",
+            Aggregate::XCorrections => "x-corrections",
+            Aggregate::ZCorrections => "z-corrections",
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Aggregate> {
+        Aggregate::ALL
+            .iter()
+            .copied()
+            .find(|aggregate| aggregate.name() == name)
+    }
+
+    // Whether the aggregate counts site (i, j): ancillas for measurement
+    // errors, data qubits otherwise.
+    pub fn counts_site(self, i: usize, j: usize) -> bool {
+        let ancilla = (i + j) % 2 == 1;
+        (self == Aggregate::MeasurementErrors) == ancilla
+    }
+}
+
+// counts[i][j] is the number of cycles, over all cases, in which site (i, j)
+// was in the aggregate's lists.
+pub fn heatmap(input: &Input, output: &Output, aggregate: Aggregate) -> Vec<Vec<usize>> {
+    let mut counts = vec![vec![0; input.d]; input.d];
+    for case in 0..input.t {
+        let instance = &input.testcase[case];
+        for cycle in 0..input.c {
+            let sites = match aggregate {
+                Aggregate::XErrors => &instance.x_depolarizing_error[cycle],
+                Aggregate::ZErrors => &instance.z_depolarizing_error[cycle],
+                Aggregate::MeasurementErrors => &instance.measure_error[cycle],
+                Aggregate::XCorrections => &output.x_correction[case][cycle],
+                Aggregate::ZCorrections => &output.z_correction[case][cycle],
+            };
+            for &(i, j) in sites {
+                counts[i][j] += 1;
+            }
+        }
+    }
+    counts
+}
@@ -0,0 +1,145 @@
+use proconio::input;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    pub d: usize,
+    pub c: usize,
+    pub e_d: f64,
+    pub e_m: f64,
+    pub t: usize,
+    pub testcase: Vec<Instance>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instance {
+    pub x_depolarizing_error: Vec<Vec<(usize, usize)>>,
+    pub z_depolarizing_error: Vec<Vec<(usize, usize)>>,
+    pub measure_error: Vec<Vec<(usize, usize)>>,
+}
+
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} {} {} {} {}",
+            self.d, self.c, self.e_d, self.e_m, self.t
+        )?;
+
+        let mut x_counter = 0;
+        for case in 0..self.t {
+            for turn in 0..self.c {
+                x_counter += self.testcase[case].x_depolarizing_error[turn].len();
+            }
+        }
+        writeln!(f, "{}", x_counter)?;
+        for case in 0..self.t {
+            for turn in 0..self.c {
+                for i in 0..self.testcase[case].x_depolarizing_error[turn].len() {
+                    writeln!(
+                        f,
+                        "{} {} {} {}",
+                        case,
+                        turn,
+                        self.testcase[case].x_depolarizing_error[turn][i].0,
+                        self.testcase[case].x_depolarizing_error[turn][i].1
+                    )?;
+                }
+            }
+        }
+
+        let mut z_counter = 0;
+        for case in 0..self.t {
+            for turn in 0..self.c {
+                z_counter += self.testcase[case].z_depolarizing_error[turn].len();
+            }
+        }
+        writeln!(f, "{}", z_counter)?;
+        for case in 0..self.t {
+            for turn in 0..self.c {
+                for i in 0..self.testcase[case].z_depolarizing_error[turn].len() {
+                    writeln!(
+                        f,
+                        "{} {} {} {}",
+                        case,
+                        turn,
+                        self.testcase[case].z_depolarizing_error[turn][i].0,
+                        self.testcase[case].z_depolarizing_error[turn][i].1
+                    )?;
+                }
+            }
+        }
+
+        let mut m_counter = 0;
+        for case in 0..self.t {
+            for turn in 0..self.c {
+                m_counter += self.testcase[case].measure_error[turn].len();
+            }
+        }
+        writeln!(f, "{}", m_counter)?;
+        for case in 0..self.t {
+            for turn in 0..self.c {
+                for i in 0..self.testcase[case].measure_error[turn].len() {
+                    writeln!(
+                        f,
+                        "{} {} {} {}",
+                        case,
+                        turn,
+                        self.testcase[case].measure_error[turn][i].0,
+                        self.testcase[case].measure_error[turn][i].1
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn parse_input(f: &str) -> Input {
+    let f = proconio::source::once::OnceSource::from(f);
+    input! {
+        from f,
+        d: usize,
+        c: usize,
+        e_d: f64,
+        e_m: f64,
+        t: usize,
+        x_size: usize,
+        x_depolarizing_turn: [(usize, usize, usize, usize); x_size],
+        z_size: usize,
+        z_depolarizing_turn: [(usize, usize, usize, usize); z_size],
+        m_size: usize,
+        measure_turn: [(usize, usize, usize, usize); m_size],
+    }
+    let mut instances = vec![];
+    for _case in 0..t {
+        instances.push(Instance {
+            x_depolarizing_error: vec![vec![]; c],
+            z_depolarizing_error: vec![vec![]; c],
+            measure_error: vec![vec![]; c],
+        });
+    }
+
+    for T in 0..x_size {
+        instances[x_depolarizing_turn[T].0].x_depolarizing_error[x_depolarizing_turn[T].1]
+            .push((x_depolarizing_turn[T].2, x_depolarizing_turn[T].3))
+    }
+    for T in 0..z_size {
+        instances[z_depolarizing_turn[T].0].z_depolarizing_error[z_depolarizing_turn[T].1]
+            .push((z_depolarizing_turn[T].2, z_depolarizing_turn[T].3))
+    }
+    for T in 0..m_size {
+        instances[measure_turn[T].0].measure_error[measure_turn[T].1]
+            .push((measure_turn[T].2, measure_turn[T].3))
+    }
+
+    Input {
+        d,
+        c,
+        e_d,
+        e_m,
+        t,
+        testcase: instances,
+    }
+}
@@ -1,33 +1,27 @@
-use wasm_bindgen::prelude::*;
-mod util;
-
-#[wasm_bindgen]
-pub fn gen(seed: i32) -> String {
-    util::gen(seed as u64).to_string()
-}
-
-#[wasm_bindgen(getter_with_clone)]
-pub struct Ret {
-    pub score: i64,
-    pub err: String,
-    pub svg: String,
-}
-
-#[wasm_bindgen]
-pub fn vis(_input: String, _output: String, turn: usize) -> Ret {
-    let input = util::parse_input(&_input);
-    let output = util::parse_output(&_output, input.c, input.t);
-    let (score, err, svg) = util::vis(&input, &output, turn as usize);
-    Ret {
-        score: score,
-        err: err.to_string(),
-        svg: svg.to_string(),
-    }
-}
-
-#[wasm_bindgen]
-pub fn get_max_turn(_input: String, _output: String) -> usize {
-    let input = util::parse_input(&_input);
-    //let output = util::parse_output(&_output);
-    input.c * 2 + 1
-}
+// Scoring, generation and rendering of the contest, usable as a Rust library.
+// The JavaScript bindings are behind the `wasm` feature and the command-line
+// tools in src/bin behind the `cli` feature.
+pub mod decoder;
+pub mod dem;
+pub mod equivalence;
+pub mod frame;
+pub mod gen;
+pub mod graph;
+pub mod heatmap;
+pub mod input;
+pub mod minimize;
+pub mod output;
+#[cfg(feature = "png")]
+pub mod raster;
+pub mod sample;
+pub mod score;
+pub mod sdk;
+pub mod stim;
+pub mod syndrome;
+pub mod theme;
+pub mod threshold;
+pub mod timeline;
+pub mod util;
+pub mod vis;
+#[cfg(feature = "wasm")]
+mod wasm;
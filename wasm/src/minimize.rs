@@ -0,0 +1,157 @@
+// Rewrites corrections into minimum-weight stabilizer-equivalent ones.
+//
+// Only the XOR of a case's corrections over all cycles matters for the score.
+// For one Pauli type, take the graph whose vertices are the ancillas of that
+// type plus two boundary vertices, and whose edges are the data qubits: the
+// boundary vertex A collects the qubits whose parity is the logical class
+// (column 0 for X, row 0 for Z), B the opposite boundary. The syndrome of a
+// correction set F is the set of ancillas of odd degree in F and its logical
+// class is the parity of A's degree, so an equivalent set is any F' with the
+// same odd-degree vertices T: a minimum T-join, found by matching T on
+// shortest-path distances. The result replaces all of the case's corrections
+// and is put in its last cycle, so the corrections move out of the cycles
+// they were made in; only the score is kept, not the timing.
+use std::collections::VecDeque;
+
+use crate::decoder::blossom::min_weight_perfect_matching;
+use crate::frame::BitGrid;
+use crate::graph::Pauli;
+use crate::input::Input;
+use crate::output::Output;
+use crate::sdk::{Lattice, OutputBuilder};
+
+#[derive(Clone, Debug)]
+pub struct Minimized {
+    pub output: Output,
+    // number of corrections of each case before and after
+    pub before: Vec<usize>,
+    pub after: Vec<usize>,
+}
+
+impl Minimized {
+    pub fn removed(&self) -> usize {
+        self.before.iter().sum::<usize>() - self.after.iter().sum::<usize>()
+    }
+}
+
+pub fn minimize(input: &Input, output: &Output) -> Minimized {
+    let lattice = Lattice::of(input);
+    let mut minimized = OutputBuilder::for_input(input);
+    let mut before = vec![];
+    let mut after = vec![];
+    for case in 0..input.t {
+        let mut count = 0;
+        let mut x = BitGrid::new(input.d);
+        let mut z = BitGrid::new(input.d);
+        for cycle in 0..input.c {
+            x.apply(&output.x_correction[case][cycle]);
+            z.apply(&output.z_correction[case][cycle]);
+            count += output.x_correction[case][cycle].len();
+            count += output.z_correction[case][cycle].len();
+        }
+        let x = minimum_equivalent(Pauli::X, &x);
+        let z = minimum_equivalent(Pauli::Z, &z);
+        before.push(count);
+        after.push(x.len() + z.len());
+        for (i, j) in x {
+            minimized.correct_x(case, input.c - 1, lattice.data_qubit(i, j).unwrap());
+        }
+        for (i, j) in z {
+            minimized.correct_z(case, input.c - 1, lattice.data_qubit(i, j).unwrap());
+        }
+    }
+    Minimized {
+        output: minimized.build(),
+        before,
+        after,
+    }
+}
+
+// A minimum-weight set of data qubits with the same syndrome and logical
+// class as `flips` for errors of the given type, in row-major order.
+pub fn minimum_equivalent(pauli: Pauli, flips: &BitGrid) -> Vec<(usize, usize)> {
+    let d = flips.d();
+    let lattice = Lattice::new(d);
+    let mut index = vec![vec![usize::MAX; d]; d];
+    let mut n = 0;
+    for site in lattice.sites() {
+        let (i, j) = site.into();
+        if pauli.is_ancilla(i, j) {
+            index[i][j] = n;
+            n += 1;
+        }
+    }
+    let (a, b) = (n, n + 1);
+
+    // every data qubit joins its one or two ancillas, or an ancilla and a boundary
+    let mut adjacency: Vec<Vec<(usize, (usize, usize))>> = vec![vec![]; n + 2];
+    let mut odd = vec![false; n + 2];
+    for q in lattice.data_qubits() {
+        let (i, j) = q.into();
+        let mut ends: Vec<usize> = lattice
+            .neighbours(i, j)
+            .map(<(usize, usize)>::from)
+            .filter(|&(i, j)| pauli.is_ancilla(i, j))
+            .map(|(i, j)| index[i][j])
+            .collect();
+        if ends.len() == 1 {
+            let line = if pauli == Pauli::X { j } else { i };
+            ends.push(if line == 0 { a } else { b });
+        }
+        if ends.len() != 2 {
+            continue;
+        }
+        adjacency[ends[0]].push((ends[1], (i, j)));
+        adjacency[ends[1]].push((ends[0], (i, j)));
+        if flips.get(i, j) {
+            odd[ends[0]] ^= true;
+            odd[ends[1]] ^= true;
+        }
+    }
+
+    let terminals: Vec<usize> = (0..n + 2).filter(|&v| odd[v]).collect();
+    // breadth-first search from every terminal: distances and the qubit used
+    // to enter each vertex
+    let mut searches = vec![];
+    for &source in &terminals {
+        let mut dist = vec![usize::MAX; n + 2];
+        let mut entry = vec![(usize::MAX, (0, 0)); n + 2];
+        dist[source] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            for &(w, qubit) in &adjacency[v] {
+                if dist[w] == usize::MAX {
+                    dist[w] = dist[v] + 1;
+                    entry[w] = (v, qubit);
+                    queue.push_back(w);
+                }
+            }
+        }
+        searches.push((dist, entry));
+    }
+
+    let mut edges = vec![];
+    for (k, (dist, _)) in searches.iter().enumerate() {
+        for (l, &terminal) in terminals.iter().enumerate().skip(k + 1) {
+            if dist[terminal] != usize::MAX {
+                edges.push((k, l, dist[terminal] as i64));
+            }
+        }
+    }
+    let mate = min_weight_perfect_matching(terminals.len(), &edges)
+        .expect("the odd vertices of a set of edges can always be paired up");
+
+    let mut result = BitGrid::new(d);
+    for (k, (&source, (_, entry))) in terminals.iter().zip(&searches).enumerate() {
+        if k > mate[k] {
+            continue;
+        }
+        let mut v = terminals[mate[k]];
+        while v != source {
+            let (previous, (i, j)) = entry[v];
+            result.flip(i, j);
+            v = previous;
+        }
+    }
+    result.ones()
+}
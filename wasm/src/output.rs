@@ -0,0 +1,151 @@
+use proconio::input;
+
+use crate::input::Input;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Output {
+    pub x_correction: Vec<Vec<Vec<(usize, usize)>>>,
+    pub z_correction: Vec<Vec<Vec<(usize, usize)>>>,
+}
+
+impl Output {
+    // The same corrections in canonical order: each cycle's corrections
+    // sorted by qubit, with pairs on the same qubit in the same cycle
+    // cancelled. Every view of the output (score, vis turns) is unchanged.
+    pub fn canonical(&self) -> Output {
+        let canonical = |corrections: &Vec<Vec<Vec<(usize, usize)>>>| {
+            corrections
+                .iter()
+                .map(|case| {
+                    case.iter()
+                        .map(|cycle| {
+                            let mut sorted = cycle.clone();
+                            sorted.sort();
+                            let mut ret: Vec<(usize, usize)> = vec![];
+                            for q in sorted {
+                                if ret.last() == Some(&q) {
+                                    ret.pop();
+                                } else {
+                                    ret.push(q);
+                                }
+                            }
+                            ret
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        Output {
+            x_correction: canonical(&self.x_correction),
+            z_correction: canonical(&self.z_correction),
+        }
+    }
+
+    // Writes the canonical form in the format `parse_output` reads.
+    pub fn to_canonical_string(&self) -> String {
+        self.canonical().to_string()
+    }
+}
+
+impl std::fmt::Display for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut x_counter = 0;
+        for case in 0..self.x_correction.len() {
+            for turn in 0..self.x_correction[case].len() {
+                x_counter += self.x_correction[case][turn].len();
+            }
+        }
+        writeln!(f, "{}", x_counter)?;
+        for case in 0..self.x_correction.len() {
+            for turn in 0..self.x_correction[case].len() {
+                for &(x, y) in &self.x_correction[case][turn] {
+                    writeln!(f, "{} {} {} {}", case, turn, x, y)?;
+                }
+            }
+        }
+
+        let mut z_counter = 0;
+        for case in 0..self.z_correction.len() {
+            for turn in 0..self.z_correction[case].len() {
+                z_counter += self.z_correction[case][turn].len();
+            }
+        }
+        writeln!(f, "{}", z_counter)?;
+        for case in 0..self.z_correction.len() {
+            for turn in 0..self.z_correction[case].len() {
+                for &(x, y) in &self.z_correction[case][turn] {
+                    writeln!(f, "{} {} {} {}", case, turn, x, y)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(
+    non_snake_case,
+    clippy::comparison_to_empty,
+    clippy::redundant_field_names
+)]
+pub fn parse_output(f: &str, c: usize, t: usize) -> Output {
+    if f == "" {
+        return Output {
+            x_correction: vec![vec![vec![]; c]; t],
+            z_correction: vec![vec![vec![]; c]; t],
+        };
+    }
+
+    let f = proconio::source::once::OnceSource::from(f);
+    input! {
+        from f,
+        x_size: usize,
+        x_correction_turn: [(usize, usize, usize, usize); x_size],
+        z_size: usize,
+        z_correction_turn: [(usize, usize, usize, usize); z_size],
+    }
+    let mut x_correction: Vec<Vec<Vec<(usize, usize)>>> = vec![vec![vec![]; c]; t];
+    let mut z_correction: Vec<Vec<Vec<(usize, usize)>>> = vec![vec![vec![]; c]; t];
+    for T in 0..x_size {
+        x_correction[x_correction_turn[T].0][x_correction_turn[T].1]
+            .push((x_correction_turn[T].2, x_correction_turn[T].3))
+    }
+    for T in 0..z_size {
+        z_correction[z_correction_turn[T].0][z_correction_turn[T].1]
+            .push((z_correction_turn[T].2, z_correction_turn[T].3))
+    }
+    Output {
+        x_correction: x_correction,
+        z_correction: z_correction,
+    }
+}
+
+// Checks that every correction targets a data qubit inside the lattice.
+#[allow(unused_parens, unused_comparisons, clippy::absurd_extreme_comparisons)]
+pub fn validate_output(input: &Input, output: &Output) -> Result<(), String> {
+    for turn in 0..input.t {
+        for i in 0..output.x_correction[turn].len() {
+            for j in 0..output.x_correction[turn][i].len() {
+                let (x, y) = output.x_correction[turn][i][j];
+                if (x >= input.d || y >= input.d || x < 0 || y < 0) {
+                    return Err(format!("テストケース {} において、サイクル {} に範囲外の座標 ({} {}) のデータ量子ビットに X エラーがあると出力しています。", turn, i, x, y));
+                }
+                if ((x + y) % 2 != 0) {
+                    return Err(format!("テストケース {} において、サイクル {} にデータ量子ビットではない座標 ({} {}) に X エラーがあると出力しています。", turn, i, x, y));
+                }
+            }
+        }
+        for i in 0..output.z_correction[turn].len() {
+            for j in 0..output.z_correction[turn][i].len() {
+                let (x, y) = output.z_correction[turn][i][j];
+                if (x >= input.d || y >= input.d || x < 0 || y < 0) {
+                    return Err(format!("テストケース {} において、サイクル {} に範囲外の座標 ({} {}) のデータ量子ビットに Z エラーがあると出力しています。", turn, i, x, y));
+                }
+                if ((x + y) % 2 != 0) {
+                    return Err(format!("テストケース {} において、サイクル {} にデータ量子ビットではない座標 ({} {}) に Z エラーがあると出力しています。", turn, i, x, y));
+                }
+            }
+        }
+    }
+    Ok(())
+}
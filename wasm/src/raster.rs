@@ -0,0 +1,80 @@
+// Rasterizes the SVGs of `vis` in-process, for native builds without a
+// browser. System fonts are loaded once per Rasterizer so that text in the
+// SVG renders.
+use std::sync::Arc;
+
+use resvg::usvg::fontdb;
+use resvg::{tiny_skia, usvg};
+
+pub struct Rasterizer {
+    options: usvg::Options<'static>,
+}
+
+impl Rasterizer {
+    pub fn new() -> Rasterizer {
+        let mut fonts = fontdb::Database::new();
+        fonts.load_system_fonts();
+        Rasterizer::with_fonts(fonts)
+    }
+
+    // Renders text with the given fonts only; without any, text is left out.
+    pub fn with_fonts(fonts: fontdb::Database) -> Rasterizer {
+        let mut options = usvg::Options {
+            fontdb: Arc::new(fonts),
+            ..usvg::Options::default()
+        };
+        let fonts = options.fontdb_mut();
+        // the generic sans-serif family defaults to Arial, which headless
+        // machines rarely have; fall back to an installed sans font, or to
+        // any font at all
+        let query = fontdb::Query {
+            families: &[fontdb::Family::SansSerif],
+            ..fontdb::Query::default()
+        };
+        let fallback = match fonts.query(&query) {
+            Some(_) => None,
+            None => {
+                let names: Vec<&String> = fonts
+                    .faces()
+                    .filter_map(|face| face.families.first())
+                    .map(|(name, _)| name)
+                    .collect();
+                names
+                    .iter()
+                    .find(|name| name.contains("Sans") && !name.contains("Mono"))
+                    .or(names.first())
+                    .map(|name| name.to_string())
+            }
+        };
+        if let Some(family) = fallback {
+            fonts.set_sans_serif_family(family.clone());
+            options.font_family = family;
+        }
+        Rasterizer { options }
+    }
+
+    // PNG of the SVG on a white background, `scale` pixels per SVG unit.
+    pub fn to_png(&self, svg: &str, scale: f32) -> Result<Vec<u8>, String> {
+        let tree = usvg::Tree::from_str(svg, &self.options).map_err(|err| err.to_string())?;
+        let size = tree
+            .size()
+            .to_int_size()
+            .scale_by(scale)
+            .ok_or_else(|| format!("cannot scale the image by {}", scale))?;
+        let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+            .ok_or_else(|| "the image is empty".to_string())?;
+        pixmap.fill(tiny_skia::Color::WHITE);
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+        pixmap.encode_png().map_err(|err| err.to_string())
+    }
+}
+
+impl Default for Rasterizer {
+    fn default() -> Rasterizer {
+        Rasterizer::new()
+    }
+}
@@ -0,0 +1,143 @@
+// Monte Carlo estimate of the logical error rates of a decoder.
+//
+// Cases are drawn from the noise model of `gen` and decoded one at a time. A
+// case fails for X (or Z) if the corrections leave a nonzero final syndrome
+// of that type, or if the logical operator ends up flipped.
+use rand::SeedableRng;
+
+use crate::decoder::Decoder;
+use crate::frame::{BitGrid, Frame};
+use crate::gen::gen_instance;
+use crate::input::Input;
+use crate::output::Output;
+use crate::score::{outcome, residual, Outcome};
+use crate::syndrome::Syndromes;
+
+// shots between two checks of the stopping rule
+const BATCH: usize = 100;
+
+// Whether the output leaves an X and a Z logical error on a case: the
+// residual of each type on its own is not `Outcome::Correct`, so the sampler
+// fails a case exactly when the judge takes points off it.
+pub fn logical_failure(input: &Input, output: &Output, case: usize) -> (bool, bool) {
+    let frame = residual(input, output, case);
+    let d = input.d;
+    let x = Frame {
+        x: frame.x,
+        z: BitGrid::new(d),
+    };
+    let z = Frame {
+        x: BitGrid::new(d),
+        z: frame.z,
+    };
+    (
+        outcome(&x) != Outcome::Correct,
+        outcome(&z) != Outcome::Correct,
+    )
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Estimate {
+    pub failures: usize,
+    pub shots: usize,
+}
+
+impl Estimate {
+    pub fn rate(&self) -> f64 {
+        if self.shots == 0 {
+            return 0.0;
+        }
+        self.failures as f64 / self.shots as f64
+    }
+
+    // Wilson score interval for the given normal quantile (1.96 for 95%).
+    pub fn wilson(&self, z: f64) -> (f64, f64) {
+        if self.shots == 0 {
+            return (0.0, 1.0);
+        }
+        let n = self.shots as f64;
+        let p = self.rate();
+        let denominator = 1.0 + z * z / n;
+        let centre = (p + z * z / (2.0 * n)) / denominator;
+        let half = z / denominator * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+        ((centre - half).max(0.0), (centre + half).min(1.0))
+    }
+
+    // Half-width of the interval relative to the rate; infinite without failures.
+    pub fn relative_error(&self, z: f64) -> f64 {
+        if self.failures == 0 {
+            return f64::INFINITY;
+        }
+        let (lo, hi) = self.wilson(z);
+        (hi - lo) / 2.0 / self.rate()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Report {
+    pub x: Estimate,
+    pub z: Estimate,
+    // either logical failed
+    pub combined: Estimate,
+}
+
+#[derive(Clone, Debug)]
+pub struct SampleConfig {
+    pub d: usize,
+    pub c: usize,
+    pub e_d: f64,
+    pub e_m: f64,
+    pub seed: u64,
+    pub max_shots: usize,
+    // stop once the combined rate is known to this relative error
+    pub target: Option<f64>,
+    // normal quantile of the confidence intervals
+    pub z: f64,
+}
+
+pub fn sample(decoder: &dyn Decoder, config: &SampleConfig) -> Report {
+    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(config.seed);
+    let empty = Estimate {
+        failures: 0,
+        shots: 0,
+    };
+    let mut report = Report {
+        x: empty,
+        z: empty,
+        combined: empty,
+    };
+    for shot in 0..config.max_shots {
+        if shot > 0 && shot % BATCH == 0 {
+            if let Some(target) = config.target {
+                if report.combined.relative_error(config.z) <= target {
+                    break;
+                }
+            }
+        }
+        let input = Input {
+            d: config.d,
+            c: config.c,
+            e_d: config.e_d,
+            e_m: config.e_m,
+            t: 1,
+            testcase: vec![gen_instance(
+                &mut rng, config.d, config.c, config.e_d, config.e_m,
+            )],
+        };
+        let (x_correction, z_correction) = decoder.decode(&Syndromes::observe(&input, 0));
+        let output = Output {
+            x_correction: vec![x_correction],
+            z_correction: vec![z_correction],
+        };
+        let (x_failed, z_failed) = logical_failure(&input, &output, 0);
+        for (estimate, failed) in [
+            (&mut report.x, x_failed),
+            (&mut report.z, z_failed),
+            (&mut report.combined, x_failed || z_failed),
+        ] {
+            estimate.shots += 1;
+            estimate.failures += failed as usize;
+        }
+    }
+    report
+}
@@ -0,0 +1,72 @@
+use crate::frame::{BitGrid, Frame};
+use crate::input::Input;
+use crate::output::Output;
+
+pub fn hakidashi(
+    x_error: Vec<Vec<usize>>,
+    z_error: Vec<Vec<usize>>,
+) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    // 掃き出す
+    let mut frame = Frame {
+        x: BitGrid::from_grid(&x_error),
+        z: BitGrid::from_grid(&z_error),
+    };
+    frame.hakidashi();
+    (frame.x.to_grid(), frame.z.to_grid())
+}
+
+// Errors left on the data qubits of one case after all cycles and corrections.
+pub fn residual(input: &Input, output: &Output, case: usize) -> Frame {
+    let mut frame = Frame::new(input.d);
+    for z in 0..input.c {
+        frame.z.apply(&input.testcase[case].z_depolarizing_error[z]);
+        frame.x.apply(&input.testcase[case].x_depolarizing_error[z]);
+        frame.z.apply(&output.z_correction[case][z]);
+        frame.x.apply(&output.x_correction[case][z]);
+    }
+    frame
+}
+
+// How a case ended, the three branches of its score: syndromes left on the
+// lattice, or none and how many of the two logical operators were flipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Faulty(usize),
+    Correct,
+    OneFlipped,
+    BothFlipped,
+}
+
+pub fn outcome(frame: &Frame) -> Outcome {
+    let faulty_syndrome_count = frame.faulty();
+    if faulty_syndrome_count > 0 {
+        return Outcome::Faulty(faulty_syndrome_count);
+    }
+    match frame.logical() {
+        (false, false) => Outcome::Correct,
+        (true, true) => Outcome::BothFlipped,
+        _ => Outcome::OneFlipped,
+    }
+}
+
+// Score of one case whose residual errors are `frame`.
+pub fn case_score(frame: &Frame) -> i64 {
+    let faulty_syndrome_count = frame.faulty();
+    let mut score = (5000.0 * (220.0 - (faulty_syndrome_count as f64)) / 220.0).round() as i64;
+
+    // bonus point!
+    match outcome(frame) {
+        // 両方正しい
+        Outcome::Correct => score += 5000,
+        // 片方は正しい
+        Outcome::OneFlipped => score += 2000,
+        Outcome::BothFlipped | Outcome::Faulty(_) => {}
+    }
+    score
+}
+
+pub fn calculate_score(input: &Input, output: &Output) -> i64 {
+    (0..input.t)
+        .map(|case| case_score(&residual(input, output, case)))
+        .sum()
+}
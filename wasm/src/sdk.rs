@@ -0,0 +1,241 @@
+// Typed lattice coordinates and an output builder for solvers.
+//
+// Site (i, j) of the d x d lattice is a data qubit when i + j is even. The
+// other sites are ancillas: an XAncilla (odd j, so even i) measures the
+// parity of the X errors on its neighbours, a ZAncilla (even j, so odd i) the
+// parity of the Z errors. X corrections cancel X errors and Z corrections Z
+// errors; both are placed on data qubits.
+use crate::input::Input;
+use crate::output::Output;
+use crate::syndrome::Syndromes;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DataQubit {
+    i: usize,
+    j: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct XAncilla {
+    i: usize,
+    j: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ZAncilla {
+    i: usize,
+    j: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Site {
+    Data(DataQubit),
+    X(XAncilla),
+    Z(ZAncilla),
+}
+
+macro_rules! coordinate {
+    ($name:ident) => {
+        impl $name {
+            pub fn i(&self) -> usize {
+                self.i
+            }
+
+            pub fn j(&self) -> usize {
+                self.j
+            }
+        }
+
+        impl From<$name> for (usize, usize) {
+            fn from(site: $name) -> (usize, usize) {
+                (site.i, site.j)
+            }
+        }
+    };
+}
+
+coordinate!(DataQubit);
+coordinate!(XAncilla);
+coordinate!(ZAncilla);
+
+impl From<Site> for (usize, usize) {
+    fn from(site: Site) -> (usize, usize) {
+        match site {
+            Site::Data(q) => q.into(),
+            Site::X(a) => a.into(),
+            Site::Z(a) => a.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lattice {
+    pub d: usize,
+}
+
+impl Lattice {
+    pub fn new(d: usize) -> Lattice {
+        Lattice { d }
+    }
+
+    pub fn of(input: &Input) -> Lattice {
+        Lattice::new(input.d)
+    }
+
+    // What sits at (i, j), or None outside the lattice.
+    pub fn site(&self, i: usize, j: usize) -> Option<Site> {
+        if i >= self.d || j >= self.d {
+            None
+        } else if (i + j).is_multiple_of(2) {
+            Some(Site::Data(DataQubit { i, j }))
+        } else if j % 2 == 1 {
+            Some(Site::X(XAncilla { i, j }))
+        } else {
+            Some(Site::Z(ZAncilla { i, j }))
+        }
+    }
+
+    pub fn data_qubit(&self, i: usize, j: usize) -> Option<DataQubit> {
+        match self.site(i, j) {
+            Some(Site::Data(q)) => Some(q),
+            _ => None,
+        }
+    }
+
+    pub fn x_ancilla(&self, i: usize, j: usize) -> Option<XAncilla> {
+        match self.site(i, j) {
+            Some(Site::X(a)) => Some(a),
+            _ => None,
+        }
+    }
+
+    pub fn z_ancilla(&self, i: usize, j: usize) -> Option<ZAncilla> {
+        match self.site(i, j) {
+            Some(Site::Z(a)) => Some(a),
+            _ => None,
+        }
+    }
+
+    // All sites in row-major order.
+    pub fn sites(&self) -> impl Iterator<Item = Site> + '_ {
+        (0..self.d).flat_map(move |i| (0..self.d).filter_map(move |j| self.site(i, j)))
+    }
+
+    pub fn data_qubits(&self) -> impl Iterator<Item = DataQubit> + '_ {
+        self.sites().filter_map(|site| match site {
+            Site::Data(q) => Some(q),
+            _ => None,
+        })
+    }
+
+    pub fn x_ancillas(&self) -> impl Iterator<Item = XAncilla> + '_ {
+        self.sites().filter_map(|site| match site {
+            Site::X(a) => Some(a),
+            _ => None,
+        })
+    }
+
+    pub fn z_ancillas(&self) -> impl Iterator<Item = ZAncilla> + '_ {
+        self.sites().filter_map(|site| match site {
+            Site::Z(a) => Some(a),
+            _ => None,
+        })
+    }
+
+    // Sites next to (i, j), in the order the scorer visits them: left,
+    // right, below, above.
+    pub fn neighbours(&self, i: usize, j: usize) -> impl Iterator<Item = Site> + '_ {
+        [(0, -1), (0, 1), (1, 0), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(di, dj)| {
+                let i = i.checked_add_signed(di)?;
+                let j = j.checked_add_signed(dj)?;
+                self.site(i, j)
+            })
+    }
+
+    // The X ancillas that detect an X error on this qubit (one or two).
+    pub fn x_neighbours(&self, q: DataQubit) -> impl Iterator<Item = XAncilla> + '_ {
+        self.neighbours(q.i, q.j).filter_map(|site| match site {
+            Site::X(a) => Some(a),
+            _ => None,
+        })
+    }
+
+    // The Z ancillas that detect a Z error on this qubit (one or two).
+    pub fn z_neighbours(&self, q: DataQubit) -> impl Iterator<Item = ZAncilla> + '_ {
+        self.neighbours(q.i, q.j).filter_map(|site| match site {
+            Site::Z(a) => Some(a),
+            _ => None,
+        })
+    }
+
+    // The data qubits an X ancilla checks (three on the boundary, else four).
+    pub fn x_stabilizer(&self, a: XAncilla) -> impl Iterator<Item = DataQubit> + '_ {
+        self.data_neighbours(a.i, a.j)
+    }
+
+    // The data qubits a Z ancilla checks (three on the boundary, else four).
+    pub fn z_stabilizer(&self, a: ZAncilla) -> impl Iterator<Item = DataQubit> + '_ {
+        self.data_neighbours(a.i, a.j)
+    }
+
+    fn data_neighbours(&self, i: usize, j: usize) -> impl Iterator<Item = DataQubit> + '_ {
+        self.neighbours(i, j).filter_map(|site| match site {
+            Site::Data(q) => Some(q),
+            _ => None,
+        })
+    }
+
+    // X ancillas measured as 1 in the given cycle.
+    pub fn measured_x(&self, syndromes: &Syndromes, cycle: usize) -> Vec<XAncilla> {
+        self.x_ancillas()
+            .filter(|a| syndromes.measured[cycle][a.i][a.j] == 1)
+            .collect()
+    }
+
+    // Z ancillas measured as 1 in the given cycle.
+    pub fn measured_z(&self, syndromes: &Syndromes, cycle: usize) -> Vec<ZAncilla> {
+        self.z_ancillas()
+            .filter(|a| syndromes.measured[cycle][a.i][a.j] == 1)
+            .collect()
+    }
+}
+
+// Collects corrections case by case and cycle by cycle. The finished Output
+// prints in the format `parse_output` reads.
+#[derive(Clone, Debug)]
+pub struct OutputBuilder {
+    output: Output,
+}
+
+impl OutputBuilder {
+    pub fn new(c: usize, t: usize) -> OutputBuilder {
+        OutputBuilder {
+            output: Output {
+                x_correction: vec![vec![vec![]; c]; t],
+                z_correction: vec![vec![vec![]; c]; t],
+            },
+        }
+    }
+
+    pub fn for_input(input: &Input) -> OutputBuilder {
+        OutputBuilder::new(input.c, input.t)
+    }
+
+    // Applies X to the qubit after the errors of the given cycle.
+    pub fn correct_x(&mut self, case: usize, cycle: usize, q: DataQubit) -> &mut OutputBuilder {
+        self.output.x_correction[case][cycle].push(q.into());
+        self
+    }
+
+    // Applies Z to the qubit after the errors of the given cycle.
+    pub fn correct_z(&mut self, case: usize, cycle: usize, q: DataQubit) -> &mut OutputBuilder {
+        self.output.z_correction[case][cycle].push(q.into());
+        self
+    }
+
+    pub fn build(self) -> Output {
+        self.output
+    }
+}
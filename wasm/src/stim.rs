@@ -0,0 +1,175 @@
+// Stim circuit of an instance's code and noise model.
+//
+// Every lattice site (i, j) is qubit i * d + j with QUBIT_COORDS(i, j). Each
+// cycle applies DEPOLARIZE1 to the data qubits at the decoders' rate (see
+// `data_error_rate`), then measures every ancilla with MR(e_m): ancillas at
+// odd j measure the Z stabilizers that detect X errors, ancillas at even j
+// the X stabilizers that detect Z errors. A noiseless projection round before
+// the first cycle puts the data qubits into a code state, so every cycle gets
+// a DETECTOR(i, j, cycle) per ancilla, comparing it with the previous round.
+//
+// Z checks run their CNOTs left, right, down, up and X checks down, up,
+// left, right: of two neighbouring checks, the Z check reaches either both
+// or neither of their shared data qubits first, so the checks commute.
+//
+// The logical operators are the ones `hakidashi` reads off at (0, 0): Z on
+// column 0 (flipped by X errors) and X on row 0 (flipped by Z errors). They
+// anticommute, so a circuit is a memory experiment for one of them: memory
+// Z resets the data qubits with R and ends with a noiseless MPP of Z_L as
+// OBSERVABLE_INCLUDE(0), memory X resets them with RX and ends with X_L as
+// OBSERVABLE_INCLUDE(1).
+use std::fmt::Write;
+
+use crate::graph::{data_error_rate, Pauli};
+use crate::input::Input;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Memory {
+    Z,
+    X,
+}
+
+impl Memory {
+    pub const ALL: [Memory; 2] = [Memory::Z, Memory::X];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Memory::Z => "z",
+            Memory::X => "x",
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Memory> {
+        Memory::ALL
+            .iter()
+            .copied()
+            .find(|memory| memory.name() == name)
+    }
+}
+
+pub fn to_stim(input: &Input, memory: Memory) -> String {
+    let d = input.d;
+    let qubit = |i: usize, j: usize| i * d + j;
+    let mut data = vec![];
+    let mut ancillas = vec![];
+    for i in 0..d {
+        for j in 0..d {
+            if (i + j) % 2 == 0 {
+                data.push(qubit(i, j));
+            } else {
+                ancillas.push((i, j));
+            }
+        }
+    }
+    let targets = |qubits: &[usize]| {
+        qubits
+            .iter()
+            .map(|q| q.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let all_ancillas: Vec<usize> = ancillas.iter().map(|&(i, j)| qubit(i, j)).collect();
+    // ancillas measuring X stabilizers, which detect Z errors
+    let x_checks: Vec<usize> = ancillas
+        .iter()
+        .filter(|&&(i, j)| Pauli::Z.is_ancilla(i, j))
+        .map(|&(i, j)| qubit(i, j))
+        .collect();
+    // Z on column 0, X on row 0
+    let z_logical: Vec<String> = (0..d)
+        .step_by(2)
+        .map(|i| format!("Z{}", qubit(i, 0)))
+        .collect();
+    let x_logical: Vec<String> = (0..d)
+        .step_by(2)
+        .map(|j| format!("X{}", qubit(0, j)))
+        .collect();
+
+    let mut s = String::new();
+    for i in 0..d {
+        for j in 0..d {
+            writeln!(s, "QUBIT_COORDS({}, {}) {}", i, j, qubit(i, j)).unwrap();
+        }
+    }
+    let (reset, logical, observable) = match memory {
+        Memory::Z => ("R", &z_logical, 0),
+        Memory::X => ("RX", &x_logical, 1),
+    };
+    writeln!(s, "{} {}", reset, targets(&data)).unwrap();
+
+    // one round of stabilizer measurements, with the given MR argument
+    let round = |s: &mut String, noise: &str| {
+        writeln!(s, "R {}", targets(&all_ancillas)).unwrap();
+        writeln!(s, "H {}", targets(&x_checks)).unwrap();
+        writeln!(s, "TICK").unwrap();
+        // (di, dj) of the neighbour in each step, Z checks then X checks
+        let left = (0, -1);
+        let right = (0, 1);
+        let down = (1, 0);
+        let up = (-1, 0);
+        let z_order = [left, right, down, up];
+        let x_order = [down, up, left, right];
+        for step in 0..4 {
+            let mut z_pairs = vec![];
+            let mut x_pairs = vec![];
+            for &(i, j) in &ancillas {
+                let (di, dj) = if Pauli::X.is_ancilla(i, j) {
+                    z_order[step]
+                } else {
+                    x_order[step]
+                };
+                let nx = i as i32 + di;
+                let ny = j as i32 + dj;
+                if nx < 0 || ny < 0 || nx >= d as i32 || ny >= d as i32 {
+                    continue;
+                }
+                let neighbour = qubit(nx as usize, ny as usize);
+                if Pauli::X.is_ancilla(i, j) {
+                    z_pairs.extend([neighbour, qubit(i, j)]);
+                } else {
+                    x_pairs.extend([qubit(i, j), neighbour]);
+                }
+            }
+            for pairs in [z_pairs, x_pairs] {
+                if !pairs.is_empty() {
+                    writeln!(s, "CX {}", targets(&pairs)).unwrap();
+                }
+            }
+            writeln!(s, "TICK").unwrap();
+        }
+        writeln!(s, "H {}", targets(&x_checks)).unwrap();
+        writeln!(s, "MR{} {}", noise, targets(&all_ancillas)).unwrap();
+    };
+
+    round(&mut s, "");
+    let n = ancillas.len();
+    for cycle in 0..input.c {
+        writeln!(s, "TICK").unwrap();
+        let e_d = data_error_rate(input.e_d, input.c, cycle);
+        if e_d > 0.0 {
+            writeln!(s, "DEPOLARIZE1({}) {}", e_d, targets(&data)).unwrap();
+        }
+        let noise = if input.e_m > 0.0 {
+            format!("({})", input.e_m)
+        } else {
+            "".to_string()
+        };
+        round(&mut s, &noise);
+        for (k, &(i, j)) in ancillas.iter().enumerate() {
+            writeln!(
+                s,
+                "DETECTOR({}, {}, {}) rec[-{}] rec[-{}]",
+                i,
+                j,
+                cycle,
+                n - k,
+                2 * n - k
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(s, "MPP {}", logical.join("*")).unwrap();
+    writeln!(s, "OBSERVABLE_INCLUDE({}) rec[-1]", observable).unwrap();
+    s
+}
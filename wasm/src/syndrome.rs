@@ -0,0 +1,73 @@
+// What a solver gets to see of one case: the lattice size, the number of
+// cycles, the noise rates and the measured syndrome of every cycle. The
+// hidden error lists of the Instance are not part of it.
+use crate::frame::Frame;
+use crate::input::Input;
+
+#[derive(Clone, Debug)]
+pub struct Syndromes {
+    pub d: usize,
+    pub c: usize,
+    pub e_d: f64,
+    pub e_m: f64,
+    // measured[cycle][i][j] is the measured value of the ancilla at (i, j),
+    // 0 at data qubits
+    pub measured: Vec<Vec<Vec<usize>>>,
+}
+
+impl Syndromes {
+    // Measures every ancilla after the data errors of each cycle, with the
+    // measurement errors of that cycle applied. Corrections are tracked in
+    // software and never show up in the measured values.
+    pub fn observe(input: &Input, case: usize) -> Syndromes {
+        let instance = &input.testcase[case];
+        let mut frame = Frame::new(input.d);
+        let mut measured = vec![];
+        for z in 0..input.c {
+            frame.x.apply(&instance.x_depolarizing_error[z]);
+            frame.z.apply(&instance.z_depolarizing_error[z]);
+            let mut syndrome = frame.syndrome();
+            syndrome.apply(&instance.measure_error[z]);
+            measured.push(syndrome.to_grid());
+        }
+        Syndromes {
+            d: input.d,
+            c: input.c,
+            e_d: input.e_d,
+            e_m: input.e_m,
+            measured,
+        }
+    }
+}
+
+// The observed syndromes of every case, laid out like the input file: the
+// header line, then the number of measured 1s and one "case cycle i j" line
+// for each of them.
+pub fn syndrome_stream(input: &Input) -> String {
+    let mut lines = vec![];
+    for case in 0..input.t {
+        let syndromes = Syndromes::observe(input, case);
+        for cycle in 0..input.c {
+            for i in 0..input.d {
+                for j in 0..input.d {
+                    if syndromes.measured[cycle][i][j] == 1 {
+                        lines.push(format!("{} {} {} {}", case, cycle, i, j));
+                    }
+                }
+            }
+        }
+    }
+    format!(
+        "{} {} {} {} {}\n{}\n{}",
+        input.d,
+        input.c,
+        input.e_d,
+        input.e_m,
+        input.t,
+        lines.len(),
+        lines
+            .iter()
+            .map(|line| format!("{}\n", line))
+            .collect::<String>()
+    )
+}
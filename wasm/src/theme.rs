@@ -0,0 +1,154 @@
+// Colours of the visualizer. The drawing code only sets CSS classes; a theme
+// turns them into the style sheet of the document, so switching themes never
+// changes the shapes.
+//
+//   cell        the white square of every site
+//   qubit, mark data qubit dots and the +/◇ stabilizer marks
+//   x, z        residual errors and measured syndromes of either type
+//   injected    errors injected in the current cycle
+//   correction  corrections applied in the current turn
+//   first, second  the two outputs of a diff view
+//   cluster-N   Union-Find clusters, N running through the cluster palette
+//   ink         also the residual of the timeline chart, whose other rows
+//               borrow the colours above, as do the tiles of the summary grid
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    pub background: String,
+    pub cell: String,
+    // lines, dots, marks, outlines and text
+    pub ink: String,
+    pub x: String,
+    pub z: String,
+    pub injected: String,
+    pub correction: String,
+    pub first: String,
+    pub second: String,
+    pub clusters: Vec<String>,
+}
+
+impl Theme {
+    pub const NAMES: [&'static str; 3] = ["light", "colour-blind", "dark"];
+
+    // The original colours of the contest visualizer.
+    pub fn light() -> Theme {
+        Theme::new(
+            "light",
+            ["#ffffff", "#ffffff", "#000000"],
+            ["#e60033", "#0095d9", "#F4E511", "#00ff00"],
+            ["#f08300", "#7b2cbf"],
+            [
+                "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1",
+                "#9c755f",
+            ],
+        )
+    }
+
+    // The Okabe-Ito palette: X and Z errors and corrections stay apart under
+    // every common form of colour blindness.
+    pub fn colour_blind() -> Theme {
+        Theme::new(
+            "colour-blind",
+            ["#ffffff", "#ffffff", "#000000"],
+            ["#D55E00", "#0072B2", "#F0E442", "#009E73"],
+            ["#E69F00", "#56B4E9"],
+            [
+                "#E69F00", "#56B4E9", "#009E73", "#F0E442", "#0072B2", "#D55E00", "#CC79A7",
+                "#000000",
+            ],
+        )
+    }
+
+    pub fn dark() -> Theme {
+        Theme::new(
+            "dark",
+            ["#121212", "#1e1e1e", "#e0e0e0"],
+            ["#ff6b81", "#4fc3f7", "#ffd54f", "#69f0ae"],
+            ["#ffb74d", "#b39ddb"],
+            [
+                "#8ab4f8", "#ffb74d", "#ff8a80", "#80cbc4", "#a5d6a7", "#fff176", "#ce93d8",
+                "#bcaaa4",
+            ],
+        )
+    }
+
+    pub fn by_name(name: &str) -> Option<Theme> {
+        match name {
+            "light" => Some(Theme::light()),
+            "colour-blind" | "color-blind" => Some(Theme::colour_blind()),
+            "dark" => Some(Theme::dark()),
+            _ => None,
+        }
+    }
+
+    fn new(
+        name: &str,
+        [background, cell, ink]: [&str; 3],
+        [x, z, injected, correction]: [&str; 4],
+        [first, second]: [&str; 2],
+        clusters: [&str; 8],
+    ) -> Theme {
+        Theme {
+            name: name.to_string(),
+            background: background.to_string(),
+            cell: cell.to_string(),
+            ink: ink.to_string(),
+            x: x.to_string(),
+            z: z.to_string(),
+            injected: injected.to_string(),
+            correction: correction.to_string(),
+            first: first.to_string(),
+            second: second.to_string(),
+            clusters: clusters.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    // Style sheet for the classes the visualizer draws with.
+    pub fn css(&self) -> String {
+        let ink = &self.ink;
+        let clusters = self
+            .clusters
+            .iter()
+            .enumerate()
+            .map(|(k, colour)| format!(".cluster-{} {{color: {}}}", k, colour));
+        [
+            format!("#vis {{background-color: {}}}", self.background),
+            format!(".cell {{fill: {}; stroke: {}}}", self.cell, ink),
+            format!(".qubit {{fill: {}; stroke: {}}}", ink, ink),
+            format!(".mark {{fill: none; stroke: {}}}", ink),
+            format!(".residual {{fill-opacity: 0.5; stroke: {}}}", ink),
+            format!(".syndrome {{stroke: {}}}", ink),
+            format!(".x {{fill: {}}}", self.x),
+            format!(".z {{fill: {}}}", self.z),
+            format!(".injected {{fill: {}; stroke: {}}}", self.injected, ink),
+            format!(".correction {{fill: {}; stroke: {}}}", self.correction, ink),
+            format!(".first {{fill: {}}}", self.first),
+            format!(".second {{fill: {}}}", self.second),
+            format!(".diff {{fill: none; stroke: {}}}", ink),
+            format!(".event {{stroke: {}}}", ink),
+            format!(".ink {{fill: {}}}", ink),
+            format!(".axis {{stroke: {}}}", ink),
+            format!(".cursor {{fill: {}; fill-opacity: 0.12}}", ink),
+            format!(".tile rect {{stroke: {}}}", ink),
+            format!(".correct {{fill: {}}}", self.correction),
+            format!(".one-flipped {{fill: {}}}", self.injected),
+            format!(".both-flipped {{fill: {}}}", self.first),
+            format!(".faulty {{fill: {}}}", self.x),
+            // tiles are light in every theme
+            ".tile text {fill: #000000}".to_string(),
+            // after the classes that also stroke, which timeline areas must not
+            ".series {fill-opacity: 0.75; stroke: none}".to_string(),
+            format!("text {{fill: {}}}", ink),
+        ]
+        .into_iter()
+        .chain(clusters)
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::light()
+    }
+}
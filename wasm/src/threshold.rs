@@ -0,0 +1,350 @@
+// Threshold sweep: logical error rate vs physical error rate for several
+// distances, written as CSV and as a log-log SVG plot.
+//
+// The threshold estimate is where the curves of two consecutive distances
+// cross: below it the larger code fails less often, above it more often.
+use svg::node::element::{Circle, Group, Line, Path, Rectangle, Style, Text};
+
+use crate::decoder::Decoder;
+use crate::gen::QUIET_CYCLES;
+use crate::sample::{sample, Report, SampleConfig};
+use crate::theme::Theme;
+
+#[derive(Clone, Debug)]
+pub struct SweepConfig {
+    pub distances: Vec<usize>,
+    pub e_ds: Vec<f64>,
+    // fixed measurement error rate; None uses e_m_ratio * e_d
+    pub e_m: Option<f64>,
+    pub e_m_ratio: f64,
+    // cycles per case; None uses d noisy cycles followed by the quiet ones
+    pub c: Option<usize>,
+    pub seed: u64,
+    pub max_shots: usize,
+    pub target: Option<f64>,
+    pub z: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Point {
+    pub d: usize,
+    pub c: usize,
+    pub e_d: f64,
+    pub e_m: f64,
+    pub report: Report,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Crossing {
+    pub d1: usize,
+    pub d2: usize,
+    pub e_d: f64,
+}
+
+// Fails if a fixed number of cycles leaves no room for noisy ones.
+pub fn sweep(decoder: &dyn Decoder, config: &SweepConfig) -> Result<Vec<Point>, String> {
+    if let Some(c) = config.c.filter(|&c| c <= QUIET_CYCLES) {
+        return Err(format!(
+            "c must be more than the {} quiet cycles, got {}",
+            QUIET_CYCLES, c
+        ));
+    }
+    let mut points = vec![];
+    for &d in &config.distances {
+        for &e_d in &config.e_ds {
+            let c = config.c.unwrap_or(d + QUIET_CYCLES);
+            let e_m = config.e_m.unwrap_or(e_d * config.e_m_ratio);
+            let report = sample(
+                decoder,
+                &SampleConfig {
+                    d,
+                    c,
+                    e_d,
+                    e_m,
+                    seed: config.seed + points.len() as u64,
+                    max_shots: config.max_shots,
+                    target: config.target,
+                    z: config.z,
+                },
+            );
+            points.push(Point {
+                d,
+                c,
+                e_d,
+                e_m,
+                report,
+            });
+        }
+    }
+    Ok(points)
+}
+
+pub fn to_csv(points: &[Point], z: f64) -> String {
+    let mut s =
+        String::from("d,c,e_d,e_m,shots,x_failures,z_failures,failures,rate,rate_low,rate_high\n");
+    for point in points {
+        let combined = &point.report.combined;
+        let (lo, hi) = combined.wilson(z);
+        s += &format!(
+            "{},{},{},{},{},{},{},{},{},{},{}\n",
+            point.d,
+            point.c,
+            point.e_d,
+            point.e_m,
+            combined.shots,
+            point.report.x.failures,
+            point.report.z.failures,
+            combined.failures,
+            combined.rate(),
+            lo,
+            hi
+        );
+    }
+    s
+}
+
+fn distances(points: &[Point]) -> Vec<usize> {
+    let mut ds: Vec<usize> = points.iter().map(|p| p.d).collect();
+    ds.sort();
+    ds.dedup();
+    ds
+}
+
+// (e_d, combined rate) of one distance, by increasing e_d
+fn curve(points: &[Point], d: usize) -> Vec<(f64, f64)> {
+    let mut ret: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|p| p.d == d)
+        .map(|p| (p.e_d, p.report.combined.rate()))
+        .collect();
+    ret.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    ret
+}
+
+// Crossings of the curves of consecutive distances, interpolated linearly in
+// log-log space. Points without failures are skipped.
+pub fn crossings(points: &[Point]) -> Vec<Crossing> {
+    let ds = distances(points);
+    let mut ret = vec![];
+    for pair in ds.windows(2) {
+        let small = curve(points, pair[0]);
+        let large = curve(points, pair[1]);
+        let mut previous: Option<(f64, f64)> = None;
+        for &(e_d, rate) in &small {
+            let Some(&(_, other)) = large.iter().find(|p| p.0 == e_d) else {
+                continue;
+            };
+            if rate <= 0.0 || other <= 0.0 {
+                continue;
+            }
+            let (x, diff) = (e_d.ln(), other.ln() - rate.ln());
+            if let Some((x0, diff0)) = previous {
+                if diff0 < 0.0 && diff >= 0.0 {
+                    let x = x0 + (x - x0) * (-diff0) / (diff - diff0);
+                    ret.push(Crossing {
+                        d1: pair[0],
+                        d2: pair[1],
+                        e_d: x.exp(),
+                    });
+                }
+            }
+            previous = Some((x, diff));
+        }
+    }
+    ret
+}
+
+// Log-log plot of the combined logical error rate with Wilson error bars, one
+// `series` group per distance in the colours of the theme's cluster palette
+// and a dashed `crossing` line at every crossing.
+pub fn plot(points: &[Point], z: f64, theme: &Theme) -> svg::Document {
+    let (width, height) = (800.0, 600.0);
+    let (left, right, top, bottom) = (80.0, 140.0, 30.0, 60.0);
+
+    let positive: Vec<&Point> = points
+        .iter()
+        .filter(|p| p.e_d > 0.0 && p.report.combined.failures > 0)
+        .collect();
+    let decade = |v: f64, up: bool| {
+        let l = v.log10();
+        if up {
+            l.ceil()
+        } else {
+            l.floor()
+        }
+    };
+    let x_min = decade(
+        points
+            .iter()
+            .map(|p| p.e_d)
+            .filter(|&e| e > 0.0)
+            .fold(f64::INFINITY, f64::min)
+            .min(1.0),
+        false,
+    );
+    let x_max = decade(points.iter().map(|p| p.e_d).fold(1e-9, f64::max), true).max(x_min + 1.0);
+    let y_min = decade(
+        positive
+            .iter()
+            .map(|p| {
+                p.report
+                    .combined
+                    .wilson(z)
+                    .0
+                    .max(p.report.combined.rate() / 10.0)
+            })
+            .fold(0.1, f64::min),
+        false,
+    );
+    let y_max = 0.0;
+    let sx = |e: f64| left + (e.log10() - x_min) / (x_max - x_min) * (width - left - right);
+    let sy =
+        |r: f64| top + (y_max - r.max(1e-300).log10()) / (y_max - y_min) * (height - top - bottom);
+
+    let mut doc = svg::Document::new()
+        .set("id", "threshold")
+        .set("viewBox", (0, 0, width, height))
+        .set("width", width)
+        .set("height", height)
+        .set("style", format!("background-color:{}", theme.background));
+    doc = doc.add(Style::new(format!(
+        "text {{font-family: sans-serif; font-size: 12px; fill: {}}} .axis {{text-anchor: middle}}",
+        theme.ink
+    )));
+    doc = doc.add(
+        Rectangle::new()
+            .set("x", left)
+            .set("y", top)
+            .set("width", width - left - right)
+            .set("height", height - top - bottom)
+            .set("fill", "none")
+            .set("stroke", theme.ink.clone()),
+    );
+
+    // decade grid and labels
+    let mut grid = Group::new()
+        .set("stroke", theme.ink.clone())
+        .set("stroke-opacity", 0.2)
+        .set("stroke-width", 1);
+    let mut labels = Group::new();
+    for k in (x_min as i32)..=(x_max as i32) {
+        let x = sx(10f64.powi(k));
+        grid = grid.add(
+            Line::new()
+                .set("x1", x)
+                .set("y1", top)
+                .set("x2", x)
+                .set("y2", height - bottom),
+        );
+        labels = labels.add(
+            Text::new()
+                .set("x", x)
+                .set("y", height - bottom + 18.0)
+                .set("class", "axis")
+                .add(svg::node::Text::new(format!("1e{}", k))),
+        );
+    }
+    for k in (y_min as i32)..=(y_max as i32) {
+        let y = sy(10f64.powi(k));
+        grid = grid.add(
+            Line::new()
+                .set("x1", left)
+                .set("y1", y)
+                .set("x2", width - right)
+                .set("y2", y),
+        );
+        labels = labels.add(
+            Text::new()
+                .set("x", left - 8.0)
+                .set("y", y + 4.0)
+                .set("text-anchor", "end")
+                .add(svg::node::Text::new(format!("1e{}", k))),
+        );
+    }
+    labels = labels
+        .add(
+            Text::new()
+                .set("x", (left + width - right) / 2.0)
+                .set("y", height - 15.0)
+                .set("class", "axis")
+                .add(svg::node::Text::new("physical error rate e_d")),
+        )
+        .add(
+            Text::new()
+                .set("x", 20.0)
+                .set("y", (top + height - bottom) / 2.0)
+                .set("class", "axis")
+                .set(
+                    "transform",
+                    format!("rotate(-90 20 {})", (top + height - bottom) / 2.0),
+                )
+                .add(svg::node::Text::new("logical error rate")),
+        );
+    doc = doc.add(grid).add(labels);
+
+    for crossing in crossings(points) {
+        let x = sx(crossing.e_d);
+        doc = doc.add(
+            Line::new()
+                .set("x1", x)
+                .set("y1", top)
+                .set("x2", x)
+                .set("y2", height - bottom)
+                .set("stroke", theme.ink.clone())
+                .set("stroke-opacity", 0.5)
+                .set("stroke-dasharray", "6,4")
+                .set("class", "crossing"),
+        );
+        doc = doc.add(Text::new().set("x", x + 4.0).set("y", top + 14.0).add(
+            svg::node::Text::new(format!(
+                "d={}/{}: {:.4}",
+                crossing.d1, crossing.d2, crossing.e_d
+            )),
+        ));
+    }
+
+    for (k, &d) in distances(points).iter().enumerate() {
+        let colour = &theme.clusters[k % theme.clusters.len()];
+        let mut series = Group::new()
+            .set("class", "series")
+            .set("stroke", colour.clone())
+            .set("fill", colour.clone());
+        let mut data = String::new();
+        for point in positive.iter().filter(|p| p.d == d) {
+            let estimate = &point.report.combined;
+            let (x, y) = (sx(point.e_d), sy(estimate.rate()));
+            data += &format!("{} {} {} ", if data.is_empty() { "M" } else { "L" }, x, y);
+            let (lo, hi) = estimate.wilson(z);
+            series = series
+                .add(
+                    Line::new()
+                        .set("x1", x)
+                        .set("y1", sy(lo.max(10f64.powf(y_min))))
+                        .set("x2", x)
+                        .set("y2", sy(hi)),
+                )
+                .add(Circle::new().set("cx", x).set("cy", y).set("r", 3));
+        }
+        if !data.is_empty() {
+            series = series.add(Path::new().set("d", data).set("fill", "none"));
+        }
+        let legend_y = top + 20.0 + 20.0 * k as f64;
+        series = series
+            .add(
+                Line::new()
+                    .set("x1", width - right + 15.0)
+                    .set("y1", legend_y)
+                    .set("x2", width - right + 40.0)
+                    .set("y2", legend_y),
+            )
+            .add(
+                Text::new()
+                    .set("x", width - right + 46.0)
+                    .set("y", legend_y + 4.0)
+                    .set("stroke", "none")
+                    .add(svg::node::Text::new(format!("d = {}", d))),
+            );
+        doc = doc.add(series);
+    }
+    doc
+}
@@ -0,0 +1,199 @@
+// Per-cycle counts of one case, the data behind the timeline chart of
+// `vis::vis_timeline`, and the history of a single site behind the strip of
+// `vis::vis_site_history`. Unlike `Syndromes::observe`, which measures the
+// errors alone, the residual syndromes and measurements here are what the
+// lattice view shows in its errors turn: the frame with the corrections of the
+// earlier cycles applied. Detection events are what the decoders see, changes
+// of the observed syndrome since the previous cycle, which the corrections
+// never touch.
+use crate::frame::Frame;
+use crate::graph::Pauli;
+use crate::input::Input;
+use crate::output::{validate_output, Output};
+use crate::syndrome::Syndromes;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CycleCounts {
+    pub x_errors: usize,
+    pub z_errors: usize,
+    pub measurement_errors: usize,
+    // ancillas measured as 1 after the cycle's errors, with the corrections
+    // of the earlier cycles applied; the raw syndrome of `Syndromes::observe`
+    // only matches it without corrections
+    pub residual_syndromes: usize,
+    pub detection_events: usize,
+    // X and Z corrections applied in the cycle
+    pub corrections: usize,
+    // weight of the residual frame after the cycle's corrections
+    pub residual: usize,
+}
+
+pub fn timeline(input: &Input, output: &Output, case: usize) -> Vec<CycleCounts> {
+    let instance = &input.testcase[case];
+    let observed = Syndromes::observe(input, case);
+    let mut frame = Frame::new(input.d);
+    let mut counts = vec![];
+    for cycle in 0..input.c {
+        frame.x.apply(&instance.x_depolarizing_error[cycle]);
+        frame.z.apply(&instance.z_depolarizing_error[cycle]);
+        let mut syndrome = frame.syndrome();
+        syndrome.apply(&instance.measure_error[cycle]);
+
+        let mut detection_events = 0;
+        for i in 0..input.d {
+            for j in 0..input.d {
+                let previous = if cycle == 0 {
+                    0
+                } else {
+                    observed.measured[cycle - 1][i][j]
+                };
+                if observed.measured[cycle][i][j] != previous {
+                    detection_events += 1;
+                }
+            }
+        }
+
+        frame.x.apply(&output.x_correction[case][cycle]);
+        frame.z.apply(&output.z_correction[case][cycle]);
+        counts.push(CycleCounts {
+            x_errors: instance.x_depolarizing_error[cycle].len(),
+            z_errors: instance.z_depolarizing_error[cycle].len(),
+            measurement_errors: instance.measure_error[cycle].len(),
+            residual_syndromes: syndrome.count_ones(),
+            detection_events,
+            corrections: output.x_correction[case][cycle].len()
+                + output.z_correction[case][cycle].len(),
+            residual: frame.x.count_ones() + frame.z.count_ones(),
+        });
+    }
+    counts
+}
+
+// What happened to a data qubit in one cycle: the errors injected on it, the
+// corrections applied to it and its residual frame after both.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DataCycle {
+    pub x_error: bool,
+    pub z_error: bool,
+    pub x_correction: bool,
+    pub z_correction: bool,
+    pub x_frame: bool,
+    pub z_frame: bool,
+}
+
+// What an ancilla saw in one cycle: the parity of the frame around it after
+// the cycle's errors, the value measured and whether the measurement was
+// flipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AncillaCycle {
+    // of the frame with the corrections of the earlier cycles applied, as in
+    // the lattice view
+    pub residual_parity: bool,
+    // that parity with the measurement error; not the raw value of
+    // `Syndromes::observe` once a correction touched the ancilla
+    pub residual_measured: bool,
+    pub measurement_error: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SiteHistory {
+    Data(Vec<DataCycle>),
+    // the type of errors the ancilla detects
+    Ancilla(Pauli, Vec<AncillaCycle>),
+}
+
+// The history of site (i, j) over every cycle of a case, once the output
+// passes `validate_output`.
+pub fn site_history(
+    input: &Input,
+    output: &Output,
+    case: usize,
+    i: usize,
+    j: usize,
+) -> Result<SiteHistory, String> {
+    validate_output(input, output)?;
+    if case >= input.t {
+        return Err(format!("case {} is out of range", case));
+    }
+    if i >= input.d || j >= input.d {
+        return Err(format!("site ({}, {}) is out of range", i, j));
+    }
+    let instance = &input.testcase[case];
+    let hit = |sites: &[(usize, usize)]| sites.contains(&(i, j));
+    let mut frame = Frame::new(input.d);
+    let mut data = vec![];
+    let mut ancilla = vec![];
+    for cycle in 0..input.c {
+        frame.x.apply(&instance.x_depolarizing_error[cycle]);
+        frame.z.apply(&instance.z_depolarizing_error[cycle]);
+        let parity = frame.syndrome().get(i, j);
+        let measurement_error = hit(&instance.measure_error[cycle]);
+        ancilla.push(AncillaCycle {
+            residual_parity: parity,
+            residual_measured: parity != measurement_error,
+            measurement_error,
+        });
+        frame.x.apply(&output.x_correction[case][cycle]);
+        frame.z.apply(&output.z_correction[case][cycle]);
+        data.push(DataCycle {
+            x_error: hit(&instance.x_depolarizing_error[cycle]),
+            z_error: hit(&instance.z_depolarizing_error[cycle]),
+            x_correction: hit(&output.x_correction[case][cycle]),
+            z_correction: hit(&output.z_correction[case][cycle]),
+            x_frame: frame.x.get(i, j),
+            z_frame: frame.z.get(i, j),
+        });
+    }
+    Ok(if Pauli::X.is_ancilla(i, j) {
+        SiteHistory::Ancilla(Pauli::X, ancilla)
+    } else if Pauli::Z.is_ancilla(i, j) {
+        SiteHistory::Ancilla(Pauli::Z, ancilla)
+    } else {
+        SiteHistory::Data(data)
+    })
+}
+
+// A header with the column names, then one line of 0s and 1s per cycle.
+impl std::fmt::Display for SiteHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bit = |b: bool| if b { 1 } else { 0 };
+        match self {
+            SiteHistory::Data(cycles) => {
+                writeln!(
+                    f,
+                    "cycle x_error z_error x_correction z_correction x_frame z_frame"
+                )?;
+                for (cycle, c) in cycles.iter().enumerate() {
+                    writeln!(
+                        f,
+                        "{} {} {} {} {} {} {}",
+                        cycle,
+                        bit(c.x_error),
+                        bit(c.z_error),
+                        bit(c.x_correction),
+                        bit(c.z_correction),
+                        bit(c.x_frame),
+                        bit(c.z_frame)
+                    )?;
+                }
+            }
+            SiteHistory::Ancilla(_, cycles) => {
+                writeln!(
+                    f,
+                    "cycle residual_parity residual_measured measurement_error"
+                )?;
+                for (cycle, c) in cycles.iter().enumerate() {
+                    writeln!(
+                        f,
+                        "{} {} {} {}",
+                        cycle,
+                        bit(c.residual_parity),
+                        bit(c.residual_measured),
+                        bit(c.measurement_error)
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1299 @@
+use std::cmp::min;
+use std::collections::HashSet;
+
+use crate::decoder::union_find;
+use crate::frame::{BitGrid, Frame};
+use crate::graph::{DecodingGraph, Pauli};
+use crate::heatmap::{heatmap, Aggregate};
+use crate::input::Input;
+use crate::output::{validate_output, Output};
+use crate::score::{calculate_score, case_score, outcome, residual, Outcome};
+use crate::syndrome::Syndromes;
+use crate::theme::Theme;
+use crate::timeline::{site_history, timeline, CycleCounts, SiteHistory};
+use svg::node::element::{
+    path::Data, Animate, Circle, Group, Line, Path, Rectangle, Style, Text, Title,
+};
+use svg::node::{Node, Text as TextNode};
+
+#[derive(Clone, Debug, Default)]
+pub struct VisOptions {
+    pub case: usize,
+    // a panel right of the lattice explaining every glyph
+    pub legend: bool,
+    // a header line above the lattice with the state of the turn
+    pub hud: bool,
+    pub theme: Theme,
+}
+
+const HUD_HEIGHT: usize = 50;
+const LEGEND_WIDTH: usize = 440;
+
+pub fn vis(input: &Input, output: &Output, turn: usize) -> (i64, String, String) {
+    vis_with(input, output, turn, &VisOptions::default())
+}
+
+// `vis` of any case of the input.
+pub fn vis_case(input: &Input, output: &Output, case: usize, turn: usize) -> (i64, String, String) {
+    vis_with(
+        input,
+        output,
+        turn,
+        &VisOptions {
+            case,
+            ..VisOptions::default()
+        },
+    )
+}
+
+pub fn vis_with(
+    input: &Input,
+    output: &Output,
+    turn: usize,
+    options: &VisOptions,
+) -> (i64, String, String) {
+    match vis_document(input, output, turn, options) {
+        Ok((score, doc)) => (score, "".to_string(), doc.to_string()),
+        Err(err) => (0, err, "".to_string()),
+    }
+}
+
+fn vis_document(
+    input: &Input,
+    output: &Output,
+    turn: usize,
+    options: &VisOptions,
+) -> Result<(i64, svg::Document), String> {
+    validate_output(input, output)?;
+    let case = options.case;
+    if case >= input.t {
+        return Err(format!("case {} is out of range", case));
+    }
+
+    let score = calculate_score(input, output);
+    let mut doc = document(&options.theme);
+    draw_turn(&mut doc, input, output, case, turn);
+    if options.hud || options.legend {
+        // the lattice keeps its coordinates, the panels go around it
+        let top = if options.hud { HUD_HEIGHT } else { 0 };
+        let right = if options.legend { LEGEND_WIDTH } else { 0 };
+        let (top, right) = (top as f64, right as f64);
+        let side = LATTICE + 2.0 * MARGIN;
+        doc = doc
+            .set(
+                "viewBox",
+                (-MARGIN, -MARGIN - top, side + right, side + top),
+            )
+            .set("width", side + right)
+            .set("height", side + top)
+            .add(Style::new(
+                ".panel text {text-anchor: start; font-size: 20px; font-family: sans-serif}",
+            ));
+    }
+    if options.hud {
+        doc.append(hud(input, output, case, turn));
+    }
+    if options.legend {
+        doc.append(legend());
+    }
+    Ok((score, doc))
+}
+
+// Case, cycle, phase, measured syndromes, residual weight and the score the
+// case would get if the game stopped after this turn.
+fn hud(input: &Input, output: &Output, case: usize, turn: usize) -> Group {
+    let (frame, syndrome) = state_at(input, output, case, turn);
+    let mut items = vec![format!("case {}", case)];
+    if turn < input.c * 2 {
+        items.push(format!("cycle {}", turn / 2));
+        items.push(
+            if turn.is_multiple_of(2) {
+                "errors"
+            } else {
+                "corrections"
+            }
+            .to_string(),
+        );
+    } else if turn == input.c * 2 {
+        items.push("final measurement".to_string());
+    } else {
+        items.push("final sweep".to_string());
+    }
+    let x = frame.x.count_ones();
+    let z = frame.z.count_ones();
+    items.push(format!("syndromes {}", syndrome.count_ones()));
+    items.push(format!("residual {} (X {}, Z {})", x + z, x, z));
+    items.push(format!("score {}", case_score(&frame)));
+    let line = items.join(" · ");
+    Group::new().set("class", "panel hud").add(
+        Text::new()
+            .set("x", 0)
+            .set("y", -20)
+            .add(TextNode::new(line)),
+    )
+}
+
+// Draws a sample glyph at (x, y) with cell size s.
+type Sample = fn(&mut Group, f64, f64, f64);
+
+// One sample glyph per row, drawn by the same functions as the lattice, with
+// its meaning next to it.
+fn legend() -> Group {
+    let s = 36.0;
+    let row = 44.0;
+    let x = LATTICE + 20.0;
+
+    let rows: [(&str, Sample); 12] = [
+        ("data qubit", |g, x, y, s| g.append(qubit_glyph(x, y, s))),
+        ("X stabilizer: detects X errors", |g, x, y, s| {
+            g.append(mark_glyph(x, y, s, Pauli::X))
+        }),
+        ("Z stabilizer: detects Z errors", |g, x, y, s| {
+            g.append(mark_glyph(x, y, s, Pauli::Z))
+        }),
+        ("X error left on the qubit", |g, x, y, s| {
+            g.append(residual_glyph(x, y, s, Pauli::X))
+        }),
+        ("Z error left on the qubit", |g, x, y, s| {
+            g.append(residual_glyph(x, y, s, Pauli::Z))
+        }),
+        ("X syndrome measured as 1", |g, x, y, s| {
+            g.append(syndrome_glyph(x, y, s, Pauli::X))
+        }),
+        ("Z syndrome measured as 1", |g, x, y, s| {
+            g.append(syndrome_glyph(x, y, s, Pauli::Z))
+        }),
+        ("X error this cycle", |g, x, y, s| {
+            g.append(injected_glyph(x, y, s, Injected::X))
+        }),
+        ("Z error this cycle", |g, x, y, s| {
+            g.append(injected_glyph(x, y, s, Injected::Z))
+        }),
+        ("measurement error this cycle", |g, x, y, s| {
+            g.append(injected_glyph(x, y, s, Injected::Measurement))
+        }),
+        ("X correction in this turn", |g, x, y, s| {
+            g.append(correction_glyph(x, y, s, Pauli::X))
+        }),
+        ("Z correction in this turn", |g, x, y, s| {
+            g.append(correction_glyph(x, y, s, Pauli::Z))
+        }),
+    ];
+    let mut group = Group::new().set("class", "panel legend");
+    for (k, (label, draw)) in rows.iter().enumerate() {
+        let y = 10.0 + k as f64 * row;
+        group.append(cell_glyph(x, y, s));
+        draw(&mut group, x, y, s);
+        group.append(
+            Text::new()
+                .set("x", x + s + 14.0)
+                .set("y", y + s / 2.0 + 7.0)
+                .add(TextNode::new(*label)),
+        );
+    }
+    group
+}
+
+// Everything `vis` shows of one case in one turn.
+fn draw_turn<N: Node>(doc: &mut N, input: &Input, output: &Output, case: usize, turn: usize) {
+    boxes(doc, input.d);
+    draw_frame(doc, input, output, case, turn);
+    qubits(doc, input.d);
+    stabilizer_marks(doc, input.d);
+    draw_events(doc, input, output, case, turn);
+}
+
+// The residual errors and the measured syndrome, drawn under the qubits.
+fn draw_frame<N: Node>(doc: &mut N, input: &Input, output: &Output, case: usize, turn: usize) {
+    let s = cell(input.d);
+    let (frame, syndrome) = state_at(input, output, case, turn);
+
+    // errors
+    for i in 0..input.d {
+        for j in 0..input.d {
+            let (x, y) = corner(i, j, s);
+            if frame.x.get(i, j) {
+                doc.append(residual_glyph(x, y, s, Pauli::X));
+            }
+            if frame.z.get(i, j) {
+                doc.append(residual_glyph(x, y, s, Pauli::Z));
+            }
+        }
+    }
+    // syndromes
+    for (i, j) in syndrome.ones() {
+        let (x, y) = corner(i, j, s);
+        let pauli = if j % 2 == 1 { Pauli::X } else { Pauli::Z };
+        doc.append(syndrome_glyph(x, y, s, pauli));
+    }
+}
+
+// The errors injected in this turn and the corrections applied in it, drawn
+// over the qubits.
+fn draw_events<N: Node>(doc: &mut N, input: &Input, output: &Output, case: usize, turn: usize) {
+    let s = cell(input.d);
+    let instance = &input.testcase[case];
+
+    if turn < input.c * 2 {
+        for &(i, j) in &instance.x_depolarizing_error[turn / 2] {
+            let (x, y) = corner(i, j, s);
+            doc.append(injected_glyph(x, y, s, Injected::X));
+        }
+        for &(i, j) in &instance.z_depolarizing_error[turn / 2] {
+            let (x, y) = corner(i, j, s);
+            doc.append(injected_glyph(x, y, s, Injected::Z));
+        }
+    }
+
+    if turn % 2 == 1 && turn < input.c * 2 {
+        for &(i, j) in &output.x_correction[case][turn / 2] {
+            let (x, y) = corner(i, j, s);
+            doc.append(correction_glyph(x, y, s, Pauli::X));
+        }
+        for &(i, j) in &output.z_correction[case][turn / 2] {
+            let (x, y) = corner(i, j, s);
+            doc.append(correction_glyph(x, y, s, Pauli::Z));
+        }
+    }
+
+    if turn < input.c * 2 {
+        for &(i, j) in &instance.measure_error[turn / 2] {
+            let (x, y) = corner(i, j, s);
+            doc.append(injected_glyph(x, y, s, Injected::Measurement));
+        }
+    }
+}
+
+// All 2c + 2 turns of one case as a single self-contained SVG. Each turn is
+// a group that a discrete SMIL animation of its visibility shows for
+// `seconds`, looping forever; viewers without SMIL show the first turn.
+pub fn vis_animation(
+    input: &Input,
+    output: &Output,
+    case: usize,
+    seconds: f64,
+    theme: &Theme,
+) -> (i64, String, String) {
+    match vis_animation_document(input, output, case, seconds, theme) {
+        Ok((score, doc)) => (score, "".to_string(), doc.to_string()),
+        Err(err) => (0, err, "".to_string()),
+    }
+}
+
+fn vis_animation_document(
+    input: &Input,
+    output: &Output,
+    case: usize,
+    seconds: f64,
+    theme: &Theme,
+) -> Result<(i64, svg::Document), String> {
+    validate_output(input, output)?;
+    if case >= input.t {
+        return Err(format!("case {} is out of range", case));
+    }
+    if seconds.is_nan() || seconds <= 0.0 {
+        return Err(format!(
+            "seconds per turn must be positive, got {}",
+            seconds
+        ));
+    }
+
+    let score = calculate_score(input, output);
+    let turns = input.c * 2 + 2;
+    // the lattice is drawn once, the layers of every turn above and below
+    // the qubits are animated
+    let mut frames = vec![];
+    let mut events = vec![];
+    for turn in 0..turns {
+        let mut frame = Group::new().set("class", "turn");
+        draw_frame(&mut frame, input, output, case, turn);
+        frames.push(frame);
+        let mut event = Group::new().set("class", "turn");
+        draw_events(&mut event, input, output, case, turn);
+        events.push(event);
+    }
+    let mut doc = document(theme);
+    boxes(&mut doc, input.d);
+    for (turn, frame) in frames.into_iter().enumerate() {
+        doc.append(shown_in_turn(frame, turn, turns, seconds));
+    }
+    qubits(&mut doc, input.d);
+    stabilizer_marks(&mut doc, input.d);
+    for (turn, event) in events.into_iter().enumerate() {
+        doc.append(shown_in_turn(event, turn, turns, seconds));
+    }
+    Ok((score, doc))
+}
+
+// Makes the group visible only during its turn of a looping animation.
+fn shown_in_turn(group: Group, turn: usize, turns: usize, seconds: f64) -> Group {
+    let key_time = |turn: usize| format!("{}", turn as f64 / turns as f64);
+    // a discrete value holds from its key time to the next one
+    let mut values = vec![];
+    let mut key_times = vec![];
+    if turn > 0 {
+        values.push("hidden");
+        key_times.push(key_time(0));
+    }
+    values.push("visible");
+    key_times.push(key_time(turn));
+    if turn + 1 < turns {
+        values.push("hidden");
+        key_times.push(key_time(turn + 1));
+    }
+    group
+        .set("visibility", if turn == 0 { "visible" } else { "hidden" })
+        .add(
+            Animate::new()
+                .set("attributeName", "visibility")
+                .set("values", values.join(";"))
+                .set("keyTimes", key_times.join(";"))
+                .set("calcMode", "discrete")
+                .set(
+                    "dur",
+                    format!("{}ms", (seconds * 1000.0 * turns as f64).round()),
+                )
+                .set("repeatCount", "indefinite"),
+        )
+}
+
+// Draws two outputs for the same input on one lattice. The residual errors of
+// the first output fill the upper half of a cell and those of the second the
+// lower half (X on the left, Z on the right), each in its own colour, and so
+// do the measured syndromes and this turn's corrections. Cells where the two
+// residuals or syndromes disagree get a dashed outline. Returns both scores.
+pub fn vis_diff(
+    input: &Input,
+    first: &Output,
+    second: &Output,
+    case: usize,
+    turn: usize,
+    theme: &Theme,
+) -> (i64, i64, String, String) {
+    match vis_diff_document(input, first, second, case, turn, theme) {
+        Ok((scores, doc)) => (scores[0], scores[1], "".to_string(), doc.to_string()),
+        Err(err) => (0, 0, err, "".to_string()),
+    }
+}
+
+fn vis_diff_document(
+    input: &Input,
+    first: &Output,
+    second: &Output,
+    case: usize,
+    turn: usize,
+    theme: &Theme,
+) -> Result<([i64; 2], svg::Document), String> {
+    validate_output(input, first).map_err(|err| format!("first output: {}", err))?;
+    validate_output(input, second).map_err(|err| format!("second output: {}", err))?;
+    if case >= input.t {
+        return Err(format!("case {} is out of range", case));
+    }
+
+    let s = cell(input.d);
+    let half = s / 2.0;
+    let colours = ["first", "second"];
+    let mut doc = document(theme);
+    boxes(&mut doc, input.d);
+
+    let outputs = [first, second];
+    let mut scores = [0; 2];
+    let mut frames = vec![];
+    let mut syndromes = vec![];
+    for k in 0..2 {
+        scores[k] = calculate_score(input, outputs[k]);
+        let mut frame = frame_at(input, outputs[k], case, turn);
+        if sweeps(input, outputs[k], turn) {
+            frame.hakidashi();
+        }
+        syndromes.push(measured_at(input, &frame, case, turn));
+        frames.push(frame);
+    }
+
+    // residual errors
+    for k in 0..2 {
+        let dy = k as f64 * half;
+        for i in 0..input.d {
+            for j in 0..input.d {
+                let (x, y) = corner(i, j, s);
+                for (errors, dx) in [(&frames[k].x, 0.0), (&frames[k].z, half)] {
+                    if errors.get(i, j) {
+                        doc.append(
+                            rectangle(x + dx, y + dy, half, half, THIN * s)
+                                .set("class", format!("box residual {}", colours[k])),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    // syndromes
+    for k in 0..2 {
+        for (i, j) in syndromes[k].ones() {
+            let (x, y) = corner(i, j, s);
+            doc.append(
+                rectangle(
+                    x + s / 6.0,
+                    y + s / 6.0 + k as f64 * s * 2.0 / 6.0,
+                    s * 4.0 / 6.0,
+                    s * 2.0 / 6.0,
+                    THIN * s,
+                )
+                .set("class", format!("box syndrome {}", colours[k])),
+            );
+        }
+    }
+    qubits(&mut doc, input.d);
+    stabilizer_marks(&mut doc, input.d);
+
+    if turn % 2 == 1 && turn < input.c * 2 {
+        // corrections of this turn, the first output's at the top corners
+        for k in 0..2 {
+            let dy = if k == 0 { 0.06 * s } else { s - 0.38 * s };
+            for (corrections, dx) in [
+                (&outputs[k].x_correction[case][turn / 2], 0.06 * s),
+                (&outputs[k].z_correction[case][turn / 2], s - 0.38 * s),
+            ] {
+                for &(i, j) in corrections {
+                    let (x, y) = corner(i, j, s);
+                    doc.append(
+                        rectangle(x + dx, y + dy, 0.32 * s, 0.32 * s, THIN * s)
+                            .set("class", format!("box correction {}", colours[k])),
+                    );
+                }
+            }
+        }
+    }
+
+    // disagreements
+    for i in 0..input.d {
+        for j in 0..input.d {
+            let differs = frames[0].x.get(i, j) != frames[1].x.get(i, j)
+                || frames[0].z.get(i, j) != frames[1].z.get(i, j)
+                || syndromes[0].get(i, j) != syndromes[1].get(i, j);
+            if differs {
+                let (x, y) = corner(i, j, s);
+                doc.append(
+                    rectangle(x + 0.06 * s, y + 0.06 * s, 0.88 * s, 0.88 * s, 0.1 * s)
+                        .set(
+                            "stroke-dasharray",
+                            format!("{},{}", px(0.2 * s), px(0.1 * s)),
+                        )
+                        .set("class", "diff"),
+                );
+            }
+        }
+    }
+    Ok((scores, doc))
+}
+
+const TIMELINE_WIDTH: f64 = 600.0;
+
+// The charts over the cycles of a case need at least one.
+fn has_cycles(input: &Input) -> Result<(), String> {
+    if input.c == 0 {
+        return Err("the input has no cycles".to_string());
+    }
+    Ok(())
+}
+
+// A chart of one case to show next to the lattice, as tall as it: one row per
+// count of `timeline::timeline` over the cycles, each on its own scale, with
+// the cycle of the current turn shaded and its values next to the labels. The
+// final turns stay on the last cycle.
+pub fn vis_timeline(
+    input: &Input,
+    output: &Output,
+    case: usize,
+    turn: usize,
+    theme: &Theme,
+) -> (i64, String, String) {
+    match vis_timeline_document(input, output, case, turn, theme) {
+        Ok((score, doc)) => (score, "".to_string(), doc.to_string()),
+        Err(err) => (0, err, "".to_string()),
+    }
+}
+
+// Label, theme class and per-cycle value of one timeline row.
+type Series = (&'static str, &'static str, fn(&CycleCounts) -> usize);
+
+fn vis_timeline_document(
+    input: &Input,
+    output: &Output,
+    case: usize,
+    turn: usize,
+    theme: &Theme,
+) -> Result<(i64, svg::Document), String> {
+    validate_output(input, output)?;
+    if case >= input.t {
+        return Err(format!("case {} is out of range", case));
+    }
+    has_cycles(input)?;
+
+    let counts = timeline(input, output, case);
+    let series: [Series; 7] = [
+        ("X errors", "x", |c| c.x_errors),
+        ("Z errors", "z", |c| c.z_errors),
+        ("measurement errors", "injected", |c| c.measurement_errors),
+        ("residual syndromes", "first", |c| c.residual_syndromes),
+        ("detection events", "second", |c| c.detection_events),
+        ("corrections", "correction", |c| c.corrections),
+        ("residual", "ink", |c| c.residual),
+    ];
+    let row = LATTICE / series.len() as f64;
+    let width = TIMELINE_WIDTH / input.c as f64;
+    let cycle = min(turn / 2, input.c - 1);
+
+    let side = LATTICE + 2.0 * MARGIN;
+    let mut doc = svg::Document::new()
+        .set("id", "vis")
+        .set(
+            "viewBox",
+            (-MARGIN, -MARGIN, TIMELINE_WIDTH + 2.0 * MARGIN, side),
+        )
+        .set("width", TIMELINE_WIDTH + 2.0 * MARGIN)
+        .set("height", side)
+        .add(Style::new(format!(
+            "text {{text-anchor: start; font-size: 18px; font-family: sans-serif}}\n{}",
+            theme.css()
+        )));
+
+    doc.append(rectangle(cycle as f64 * width, 0.0, width, LATTICE, 0.0).set("class", "cursor"));
+
+    for (k, (label, class, count)) in series.iter().enumerate() {
+        let top = k as f64 * row;
+        let values: Vec<usize> = counts.iter().map(count).collect();
+        let max = values.iter().copied().max().unwrap_or(0);
+        // the plot takes the row below its label, leaving a gap to the next
+        let (plot, bottom) = (top + 30.0, top + row - 10.0);
+        let y = |v: usize| bottom - (bottom - plot) * v as f64 / max.max(1) as f64;
+
+        let mut data = Data::new().move_to((0.0, px(bottom)));
+        for (cycle, &v) in values.iter().enumerate() {
+            data = data
+                .line_to((px(cycle as f64 * width), px(y(v))))
+                .line_to((px((cycle + 1) as f64 * width), px(y(v))));
+        }
+        data = data.line_to((TIMELINE_WIDTH, px(bottom))).close();
+        doc.append(
+            Path::new()
+                .set("d", data)
+                .set("class", format!("series {}", class)),
+        );
+        doc.append(
+            Line::new()
+                .set("x1", 0)
+                .set("y1", px(bottom))
+                .set("x2", TIMELINE_WIDTH)
+                .set("y2", px(bottom))
+                .set("stroke-width", 1)
+                .set("class", "axis"),
+        );
+        doc.append(
+            Text::new()
+                .set("x", 0)
+                .set("y", px(top + 20.0))
+                .add(TextNode::new(format!(
+                    "{} {} · max {}",
+                    label, values[cycle], max
+                ))),
+        );
+    }
+    Ok((calculate_score(input, output), doc))
+}
+
+const STRIP_LABEL: f64 = 200.0;
+const STRIP_ROW: f64 = 36.0;
+
+// The history of one site as a strip chart as wide as the timeline: a title
+// line, then one row per flag of `timeline::site_history` with a filled box in
+// every cycle it is set, and the cycle of the current turn shaded.
+pub fn vis_site_history(
+    input: &Input,
+    output: &Output,
+    case: usize,
+    (i, j): (usize, usize),
+    turn: usize,
+    theme: &Theme,
+) -> (i64, String, String) {
+    let history = has_cycles(input).and_then(|()| site_history(input, output, case, i, j));
+    match history {
+        Ok(history) => (
+            calculate_score(input, output),
+            "".to_string(),
+            site_history_document(input, case, (i, j), &history, turn, theme).to_string(),
+        ),
+        Err(err) => (0, err, "".to_string()),
+    }
+}
+
+// Label, theme class and per-cycle flags of one site history row.
+type Row = (&'static str, &'static str, Vec<bool>);
+
+fn site_history_document(
+    input: &Input,
+    case: usize,
+    (i, j): (usize, usize),
+    history: &SiteHistory,
+    turn: usize,
+    theme: &Theme,
+) -> svg::Document {
+    let (title, rows): (String, Vec<Row>) = match history {
+        SiteHistory::Data(cycles) => (
+            format!("case {} · data qubit ({}, {})", case, i, j),
+            vec![
+                ("X error", "x", cycles.iter().map(|c| c.x_error).collect()),
+                ("Z error", "z", cycles.iter().map(|c| c.z_error).collect()),
+                (
+                    "X correction",
+                    "correction",
+                    cycles.iter().map(|c| c.x_correction).collect(),
+                ),
+                (
+                    "Z correction",
+                    "correction",
+                    cycles.iter().map(|c| c.z_correction).collect(),
+                ),
+                (
+                    "X in frame",
+                    "x",
+                    cycles.iter().map(|c| c.x_frame).collect(),
+                ),
+                (
+                    "Z in frame",
+                    "z",
+                    cycles.iter().map(|c| c.z_frame).collect(),
+                ),
+            ],
+        ),
+        SiteHistory::Ancilla(pauli, cycles) => {
+            let (name, class) = if *pauli == Pauli::X {
+                ("X", "x")
+            } else {
+                ("Z", "z")
+            };
+            (
+                format!("case {} · {} ancilla ({}, {})", case, name, i, j),
+                vec![
+                    (
+                        "residual parity",
+                        "ink",
+                        cycles.iter().map(|c| c.residual_parity).collect(),
+                    ),
+                    (
+                        "residual measured",
+                        class,
+                        cycles.iter().map(|c| c.residual_measured).collect(),
+                    ),
+                    (
+                        "measurement error",
+                        "injected",
+                        cycles.iter().map(|c| c.measurement_error).collect(),
+                    ),
+                ],
+            )
+        }
+    };
+
+    let width = TIMELINE_WIDTH / input.c as f64;
+    let cycle = min(turn / 2, input.c - 1);
+    let (full, height) = (
+        STRIP_LABEL + TIMELINE_WIDTH,
+        STRIP_ROW * (rows.len() + 1) as f64,
+    );
+    let mut doc = svg::Document::new()
+        .set("id", "vis")
+        .set(
+            "viewBox",
+            (-MARGIN, -MARGIN, full + 2.0 * MARGIN, height + 2.0 * MARGIN),
+        )
+        .set("width", full + 2.0 * MARGIN)
+        .set("height", height + 2.0 * MARGIN)
+        .add(Style::new(format!(
+            "text {{text-anchor: start; dominant-baseline: central; font-size: 18px; font-family: sans-serif}}\n{}",
+            theme.css()
+        )));
+    doc.append(
+        Text::new()
+            .set("x", 0)
+            .set("y", STRIP_ROW / 2.0)
+            .add(TextNode::new(title)),
+    );
+    doc.append(
+        rectangle(
+            STRIP_LABEL + cycle as f64 * width,
+            STRIP_ROW,
+            width,
+            height - STRIP_ROW,
+            0.0,
+        )
+        .set("class", "cursor"),
+    );
+    for (k, (label, class, set)) in rows.iter().enumerate() {
+        let top = STRIP_ROW * (k + 1) as f64;
+        doc.append(
+            Text::new()
+                .set("x", 0)
+                .set("y", px(top + STRIP_ROW / 2.0))
+                .add(TextNode::new(*label)),
+        );
+        doc.append(
+            Line::new()
+                .set("x1", STRIP_LABEL)
+                .set("y1", px(top + STRIP_ROW - 4.0))
+                .set("x2", full)
+                .set("y2", px(top + STRIP_ROW - 4.0))
+                .set("stroke-width", 1)
+                .set("class", "axis"),
+        );
+        for (cycle, &on) in set.iter().enumerate() {
+            if on {
+                doc.append(
+                    rectangle(
+                        STRIP_LABEL + cycle as f64 * width,
+                        top + 4.0,
+                        width,
+                        STRIP_ROW - 8.0,
+                        0.0,
+                    )
+                    .set("class", format!("series {}", class)),
+                );
+            }
+        }
+    }
+    doc
+}
+
+// One tile per case on a square grid the size of the lattice, in case order
+// row by row, coloured by the case's `Outcome` and showing its number of
+// faulty syndromes. Every tile carries its case index in `data-case`.
+pub fn vis_summary(input: &Input, output: &Output, theme: &Theme) -> (i64, String, String) {
+    match validate_output(input, output) {
+        Ok(()) => (
+            calculate_score(input, output),
+            "".to_string(),
+            summary_document(input, output, theme).to_string(),
+        ),
+        Err(err) => (0, err, "".to_string()),
+    }
+}
+
+fn summary_document(input: &Input, output: &Output, theme: &Theme) -> svg::Document {
+    let mut columns = 1;
+    while columns * columns < input.t {
+        columns += 1;
+    }
+    let s = LATTICE / columns as f64;
+    let mut doc = document(theme).add(Style::new(format!(
+        ".tile .index {{text-anchor: start; font-size: {}px}}\n.tile .count {{font-size: {}px}}",
+        px(0.18 * s),
+        px(0.4 * s)
+    )));
+    for case in 0..input.t {
+        let frame = residual(input, output, case);
+        let (class, faulty) = match outcome(&frame) {
+            Outcome::Correct => ("correct", 0),
+            Outcome::OneFlipped => ("one-flipped", 0),
+            Outcome::BothFlipped => ("both-flipped", 0),
+            Outcome::Faulty(count) => ("faulty", count),
+        };
+        let (x, y) = corner(case / columns, case % columns, s);
+        let mut tile = Group::new()
+            .set("class", format!("tile {}", class))
+            .set("data-case", case);
+        tile.append(Title::new().add(TextNode::new(format!(
+            "case {}: {}, {} faulty syndromes, score {}",
+            case,
+            class.replace('-', " "),
+            faulty,
+            case_score(&frame)
+        ))));
+        tile.append(rectangle(x, y, s, s, THIN * s));
+        tile.append(
+            Text::new()
+                .set("class", "index")
+                .set("x", px(x + 0.06 * s))
+                .set("y", px(y + 0.15 * s))
+                .add(TextNode::new(case.to_string())),
+        );
+        tile.append(
+            Text::new()
+                .set("class", "count")
+                .set("x", px(x + s / 2.0))
+                .set("y", px(y + 0.55 * s))
+                .add(TextNode::new(faulty.to_string())),
+        );
+        doc.append(tile);
+    }
+    doc
+}
+
+// An aggregate of `heatmap::heatmap` on the lattice, each site shaded by its
+// count.
+pub fn vis_heatmap(
+    input: &Input,
+    output: &Output,
+    aggregate: Aggregate,
+    theme: &Theme,
+) -> (i64, String, String) {
+    match validate_output(input, output) {
+        Ok(()) => (
+            calculate_score(input, output),
+            "".to_string(),
+            heatmap_document(input, output, aggregate, theme).to_string(),
+        ),
+        Err(err) => (0, err, "".to_string()),
+    }
+}
+
+fn heatmap_document(
+    input: &Input,
+    output: &Output,
+    aggregate: Aggregate,
+    theme: &Theme,
+) -> svg::Document {
+    let s = cell(input.d);
+    let counts = heatmap(input, output, aggregate);
+    let counted: Vec<usize> = (0..input.d)
+        .flat_map(|i| (0..input.d).map(move |j| (i, j)))
+        .filter(|&(i, j)| aggregate.counts_site(i, j))
+        .map(|(i, j)| counts[i][j])
+        .collect();
+    let min = counted.iter().copied().min().unwrap_or(0);
+    let max = counted.iter().copied().max().unwrap_or(0);
+    // a site's share of the range between the least and the most hit sites,
+    // so that small biases still stand out
+    let shade = |count: usize| {
+        if max == min {
+            1.0
+        } else {
+            0.1 + 0.9 * (count - min) as f64 / (max - min) as f64
+        }
+    };
+    let class = match aggregate {
+        Aggregate::XErrors => "x",
+        Aggregate::ZErrors => "z",
+        Aggregate::MeasurementErrors => "injected",
+        Aggregate::XCorrections | Aggregate::ZCorrections => "correction",
+    };
+
+    let mut doc = document(theme).add(Style::new(format!(
+        ".heat {{stroke: none}}\n.count {{font-size: {}px}}",
+        px(0.3 * s)
+    )));
+    boxes(&mut doc, input.d);
+    if aggregate == Aggregate::MeasurementErrors {
+        qubits(&mut doc, input.d);
+    } else {
+        stabilizer_marks(&mut doc, input.d);
+    }
+    for (i, row) in counts.iter().enumerate() {
+        for (j, &count) in row.iter().enumerate() {
+            if !aggregate.counts_site(i, j) {
+                continue;
+            }
+            let (x, y) = corner(i, j, s);
+            if count > 0 {
+                doc.append(
+                    rectangle(x, y, s, s, 0.0)
+                        .set("fill-opacity", px(shade(count)))
+                        .set("class", format!("heat {}", class)),
+                );
+            }
+            doc.append(
+                Text::new()
+                    .set("class", "count")
+                    .set("x", px(x + s / 2.0))
+                    .set("y", px(y + s / 2.0))
+                    .add(TextNode::new(count.to_string())),
+            );
+        }
+    }
+    doc
+}
+
+fn document(theme: &Theme) -> svg::Document {
+    let side = LATTICE + 2.0 * MARGIN;
+    svg::Document::new()
+        .set("id", "vis")
+        .set("viewBox", (-MARGIN, -MARGIN, side, side))
+        .set("width", side)
+        .set("height", side)
+        .add(Style::new(format!(
+            "text {{text-anchor: middle;dominant-baseline: central; font-size: {}}}\n{}",
+            6,
+            theme.css()
+        )))
+}
+
+// The d x d cells fill LATTICE x LATTICE units for any d, site (i, j) being
+// the cell in row i and column j. Every glyph is sized relative to its cell;
+// the fractions below are the proportions of the original 1000 / 21 pixel
+// cells.
+const LATTICE: f64 = 1000.0;
+const MARGIN: f64 = 5.0;
+// hairlines, marks, circles
+const THIN: f64 = 0.02;
+const THICK: f64 = 0.085;
+const RADIUS: f64 = 0.17;
+
+fn cell(d: usize) -> f64 {
+    LATTICE / d as f64
+}
+
+// Top left corner of the cell of site (i, j).
+fn corner(i: usize, j: usize, s: f64) -> (f64, f64) {
+    (j as f64 * s, i as f64 * s)
+}
+
+// Rounds a coordinate to a hundredth of a unit, which keeps the documents
+// small.
+fn px(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+fn rectangle(x: f64, y: f64, width: f64, height: f64, stroke: f64) -> Rectangle {
+    Rectangle::new()
+        .set("x", px(x))
+        .set("y", px(y))
+        .set("width", px(width))
+        .set("height", px(height))
+        .set("stroke-width", px(stroke))
+}
+
+fn circle(cx: f64, cy: f64, r: f64, stroke: f64) -> Circle {
+    Circle::new()
+        .set("cx", px(cx))
+        .set("cy", px(cy))
+        .set("r", px(r))
+        .set("stroke-width", px(stroke))
+}
+
+// The glyphs below take the top left corner and the side of a cell.
+
+fn cell_glyph(x: f64, y: f64, s: f64) -> Rectangle {
+    rectangle(x, y, s, s, THIN * s).set("class", "box cell")
+}
+
+fn qubit_glyph(x: f64, y: f64, s: f64) -> Circle {
+    circle(x + s / 2.0, y + s / 2.0, RADIUS * s, THIN * s).set("class", "box qubit")
+}
+
+// + on the ancillas that detect X errors, ◇ on those that detect Z errors.
+fn mark_glyph(x: f64, y: f64, s: f64, pauli: Pauli) -> Path {
+    let (cx, cy, h) = (px(x + s / 2.0), px(y + s / 2.0), px(0.21 * s));
+    let data = if pauli == Pauli::X {
+        Data::new()
+            .move_to((cx - h, cy))
+            .horizontal_line_by(2.0 * h)
+            .move_to((cx, cy - h))
+            .vertical_line_by(2.0 * h)
+    } else {
+        Data::new()
+            .move_to((cx - h, cy))
+            .line_by((h, -h))
+            .line_by((h, h))
+            .line_by((-h, h))
+            .close()
+    };
+    Path::new()
+        .set("stroke-width", px(THICK * s))
+        .set("class", "mark")
+        .set("d", data)
+}
+
+// X errors fill the left half of the cell, Z errors the right half.
+fn residual_glyph(x: f64, y: f64, s: f64, pauli: Pauli) -> Rectangle {
+    let (dx, class) = match pauli {
+        Pauli::X => (0.0, "box residual x"),
+        Pauli::Z => (s / 2.0, "box residual z"),
+    };
+    rectangle(x + dx, y, s / 2.0, s, THIN * s).set("class", class)
+}
+
+fn syndrome_glyph(x: f64, y: f64, s: f64, pauli: Pauli) -> Rectangle {
+    let class = match pauli {
+        Pauli::X => "box syndrome x",
+        Pauli::Z => "box syndrome z",
+    };
+    rectangle(
+        x + s / 6.0,
+        y + s / 6.0,
+        s * 4.0 / 6.0,
+        s * 4.0 / 6.0,
+        THIN * s,
+    )
+    .set("class", class)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Injected {
+    X,
+    Z,
+    Measurement,
+}
+
+// X errors in the top left corner, Z errors in the top right one and
+// measurement errors, which only happen on ancillas, in the bottom left one.
+fn injected_glyph(x: f64, y: f64, s: f64, kind: Injected) -> Circle {
+    let offset = 0.21 * s;
+    let (cx, cy) = match kind {
+        Injected::X => (x + offset, y + offset),
+        Injected::Z => (x + s - offset, y + offset),
+        Injected::Measurement => (x + offset, y + s - offset),
+    };
+    circle(cx, cy, RADIUS * s, 0.064 * s).set("class", "box injected")
+}
+
+// X corrections in the bottom left corner, Z corrections in the bottom right.
+fn correction_glyph(x: f64, y: f64, s: f64, pauli: Pauli) -> Rectangle {
+    let dx = match pauli {
+        Pauli::X => 0.06 * s,
+        Pauli::Z => s - 0.38 * s,
+    };
+    rectangle(x + dx, y + s - 0.38 * s, 0.32 * s, 0.32 * s, THIN * s).set("class", "box correction")
+}
+
+// One cell per site.
+fn boxes<N: Node>(doc: &mut N, d: usize) {
+    let s = cell(d);
+    for i in 0..d {
+        for j in 0..d {
+            let (x, y) = corner(i, j, s);
+            doc.append(cell_glyph(x, y, s));
+        }
+    }
+}
+
+// Errors left on the data qubits of a case after this turn: the errors of
+// turn 2k come with cycle k, the corrections of turn 2k + 1 after them.
+fn frame_at(input: &Input, output: &Output, case: usize, turn: usize) -> Frame {
+    let mut frame = Frame::new(input.d);
+    for z in 0..(min((turn / 2) + 1, input.c)) {
+        frame.z.apply(&input.testcase[case].z_depolarizing_error[z]);
+        frame.x.apply(&input.testcase[case].x_depolarizing_error[z]);
+    }
+    for z in 0..(min(turn.div_ceil(2), input.c)) {
+        frame.z.apply(&output.z_correction[case][z]);
+        frame.x.apply(&output.x_correction[case][z]);
+    }
+    frame
+}
+
+// The frame `vis` shows in this turn and its measured syndrome.
+fn state_at(input: &Input, output: &Output, case: usize, turn: usize) -> (Frame, BitGrid) {
+    let mut frame = frame_at(input, output, case, turn);
+    if sweeps(input, output, turn) {
+        frame.hakidashi();
+    }
+    let syndrome = measured_at(input, &frame, case, turn);
+    (frame, syndrome)
+}
+
+// The last turn sweeps the residual to (0, 0) with `hakidashi` once the
+// output scores at least 5000 in total, as the original `vis` did.
+fn sweeps(input: &Input, output: &Output, turn: usize) -> bool {
+    turn == input.c * 2 + 1 && calculate_score(input, output) >= 5000
+}
+
+// Syndrome of the frame as measured in this turn, with the measurement
+// errors of its cycle; the final turns measure without errors.
+fn measured_at(input: &Input, frame: &Frame, case: usize, turn: usize) -> BitGrid {
+    let mut syndrome = frame.syndrome();
+    if turn < input.c * 2 {
+        syndrome.apply(&input.testcase[case].measure_error[turn / 2]);
+    }
+    syndrome
+}
+
+// A dot on every data qubit.
+fn qubits<N: Node>(doc: &mut N, d: usize) {
+    let s = cell(d);
+    for i in 0..d {
+        for j in 0..d {
+            if (i + j) % 2 == 0 {
+                let (x, y) = corner(i, j, s);
+                doc.append(qubit_glyph(x, y, s));
+            }
+        }
+    }
+}
+
+// The stabilizer mark of every ancilla.
+fn stabilizer_marks<N: Node>(doc: &mut N, d: usize) {
+    let s = cell(d);
+    for i in 0..d {
+        for j in 0..d {
+            if (i + j) % 2 == 1 {
+                let (x, y) = corner(i, j, s);
+                let pauli = if j % 2 == 1 { Pauli::X } else { Pauli::Z };
+                doc.append(mark_glyph(x, y, s, pauli));
+            }
+        }
+    }
+}
+
+// Union-Find growth steps of both decoding graphs for the given case.
+type UnionFindSteps = Vec<(DecodingGraph, Vec<usize>, Vec<Vec<usize>>)>;
+
+fn union_find_steps(input: &Input, case: usize) -> Result<UnionFindSteps, String> {
+    if case >= input.t {
+        return Err(format!("case {} is out of range", case));
+    }
+    let mut ret = vec![];
+    for pauli in [Pauli::X, Pauli::Z] {
+        let graph = DecodingGraph::new(input, pauli);
+        let events = graph.detection_events(&Syndromes::observe(input, case));
+        let (_, steps) = union_find::decode_graph_traced(&graph, &events);
+        ret.push((graph, events, steps));
+    }
+    Ok(ret)
+}
+
+pub fn get_max_union_find_step(input: &Input, case: usize) -> Result<usize, String> {
+    let steps = union_find_steps(input, case)?;
+    Ok(steps
+        .iter()
+        .map(|(_, _, steps)| steps.len())
+        .max()
+        .unwrap_or(0))
+}
+
+// Draws the Union-Find clusters of a case after `step` growth steps on top of
+// the lattice of the given turn: grown edges of the current round, ancillas in
+// a cluster and the detection events. X clusters are solid, Z clusters dashed,
+// and merged components take turns through the theme's cluster palette.
+pub fn vis_union_find(
+    input: &Input,
+    output: &Output,
+    case: usize,
+    turn: usize,
+    step: usize,
+    theme: &Theme,
+) -> (i64, String, String) {
+    let options = VisOptions {
+        case,
+        theme: theme.clone(),
+        ..VisOptions::default()
+    };
+    let (score, mut doc) = match vis_document(input, output, turn, &options) {
+        Ok(ret) => ret,
+        Err(err) => return (0, err, "".to_string()),
+    };
+    if input.c == 0 {
+        // no rounds, so nothing to grow
+        return (score, "".to_string(), doc.to_string());
+    }
+    let s = cell(input.d);
+    let cycle = min(turn / 2, input.c - 1);
+    let center = |(i, j): (usize, usize)| {
+        let (x, y) = corner(i, j, s);
+        (px(x + s / 2.0), px(y + s / 2.0))
+    };
+
+    let steps = match union_find_steps(input, case) {
+        Ok(steps) => steps,
+        Err(err) => return (0, err, "".to_string()),
+    };
+    for (graph, events, steps) in steps {
+        let dash = if graph.pauli == Pauli::X {
+            "none".to_string()
+        } else {
+            format!("{},{}", px(0.13 * s), px(0.085 * s))
+        };
+        let grown: HashSet<usize> = steps.iter().take(step).flatten().copied().collect();
+        let clusters = union_find::clusters_at(&graph, &events, &steps, step);
+        let mut cluster_of = vec![0; graph.num_nodes()];
+        for (k, cluster) in clusters.iter().enumerate() {
+            for &v in cluster {
+                cluster_of[v] = k;
+            }
+        }
+
+        let mut group = Group::new().set("class", "union-find");
+        for (e, edge) in graph.edges.iter().enumerate() {
+            if !grown.contains(&e) {
+                continue;
+            }
+            let class = cluster_class(theme, cluster_of[edge.u]);
+            let (x1, y1) = center(graph.position(edge.u).1);
+            match edge.qubit {
+                Some(qubit) if edge.cycle == cycle => {
+                    // data error edge, to the other ancilla or to the boundary qubit
+                    let (x2, y2) = center(edge.v.map_or(qubit, |v| graph.position(v).1));
+                    group = group.add(
+                        Line::new()
+                            .set("x1", x1)
+                            .set("y1", y1)
+                            .set("x2", x2)
+                            .set("y2", y2)
+                            .set("stroke", "currentColor")
+                            .set("stroke-width", px(0.13 * s))
+                            .set("stroke-dasharray", dash.clone())
+                            .set("stroke-linecap", "round")
+                            .set("class", class),
+                    );
+                }
+                None if edge.cycle == cycle || edge.cycle + 1 == cycle => {
+                    // measurement error edge into or out of this round
+                    group = group.add(
+                        Circle::new()
+                            .set("cx", x1)
+                            .set("cy", y1)
+                            .set("r", px(s * 2.0 / 5.0))
+                            .set("fill", "none")
+                            .set("stroke", "currentColor")
+                            .set("stroke-width", px(0.064 * s))
+                            .set("stroke-dasharray", dash.clone())
+                            .set("class", class),
+                    );
+                }
+                _ => {}
+            }
+        }
+        for (k, cluster) in clusters.iter().enumerate() {
+            for &v in cluster {
+                let (round, ancilla) = graph.position(v);
+                if round != cycle {
+                    continue;
+                }
+                let (cx, cy) = center(ancilla);
+                group = group.add(
+                    Circle::new()
+                        .set("cx", cx)
+                        .set("cy", cy)
+                        .set("r", px(s / 4.0))
+                        .set("fill", "currentColor")
+                        .set("fill-opacity", 0.4)
+                        .set("class", cluster_class(theme, k)),
+                );
+            }
+        }
+        for &v in &events {
+            let (round, ancilla) = graph.position(v);
+            if round != cycle {
+                continue;
+            }
+            let (cx, cy) = center(ancilla);
+            group = group.add(
+                Circle::new()
+                    .set("cx", cx)
+                    .set("cy", cy)
+                    .set("r", px(s / 8.0))
+                    .set("fill", "currentColor")
+                    .set("stroke-width", px(THIN * s))
+                    .set(
+                        "class",
+                        format!("event {}", cluster_class(theme, cluster_of[v])),
+                    ),
+            );
+        }
+        doc = doc.add(group);
+    }
+    (score, "".to_string(), doc.to_string())
+}
+
+// Cluster k sets `currentColor` to an entry of the theme's palette.
+fn cluster_class(theme: &Theme, k: usize) -> String {
+    format!("cluster-{}", k % theme.clusters.len())
+}
@@ -0,0 +1,330 @@
+// JavaScript bindings used by the web visualizer. Exports that take a case
+// and a turn take the case first.
+use wasm_bindgen::prelude::*;
+
+use crate::heatmap::Aggregate;
+use crate::theme::Theme;
+use crate::{decoder, dem, stim, timeline, util, vis};
+
+#[wasm_bindgen]
+pub fn gen(seed: i32) -> String {
+    util::gen(seed as u64).to_string()
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct Ret {
+    pub score: i64,
+    pub err: String,
+    pub svg: String,
+}
+
+#[wasm_bindgen]
+pub fn vis(_input: String, _output: String, turn: usize) -> Ret {
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    let (score, err, svg) = util::vis(&input, &output, turn);
+    Ret {
+        score,
+        err: err.to_string(),
+        svg: svg.to_string(),
+    }
+}
+
+// Every turn of one case as an animated SVG, `seconds` per turn.
+#[wasm_bindgen]
+pub fn vis_animation(
+    _input: String,
+    _output: String,
+    case: usize,
+    seconds: f64,
+    theme: String,
+) -> Ret {
+    let theme = match find_theme(&theme) {
+        Ok(theme) => theme,
+        Err(err) => return err,
+    };
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    let (score, err, svg) = vis::vis_animation(&input, &output, case, seconds, &theme);
+    Ret { score, err, svg }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct DiffRet {
+    pub score_first: i64,
+    pub score_second: i64,
+    pub err: String,
+    pub svg: String,
+}
+
+// Both outputs' residuals and corrections of one turn of a case on the same
+// lattice.
+#[wasm_bindgen]
+pub fn vis_diff(
+    _input: String,
+    _first: String,
+    _second: String,
+    case: usize,
+    turn: usize,
+    theme: String,
+) -> DiffRet {
+    let theme = match find_theme(&theme) {
+        Ok(theme) => theme,
+        Err(err) => {
+            return DiffRet {
+                score_first: 0,
+                score_second: 0,
+                err: err.err,
+                svg: err.svg,
+            }
+        }
+    };
+    let input = util::parse_input(&_input);
+    let first = util::parse_output(&_first, input.c, input.t);
+    let second = util::parse_output(&_second, input.c, input.t);
+    let (score_first, score_second, err, svg) =
+        vis::vis_diff(&input, &first, &second, case, turn, &theme);
+    DiffRet {
+        score_first,
+        score_second,
+        err,
+        svg,
+    }
+}
+
+// `vis` of any case, optionally with the legend panel and the header line,
+// in one of the themes of `list_themes`.
+#[wasm_bindgen]
+pub fn vis_with_options(
+    _input: String,
+    _output: String,
+    case: usize,
+    turn: usize,
+    legend: bool,
+    hud: bool,
+    theme: String,
+) -> Ret {
+    let theme = match find_theme(&theme) {
+        Ok(theme) => theme,
+        Err(err) => return err,
+    };
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    let options = vis::VisOptions {
+        case,
+        legend,
+        hud,
+        theme,
+    };
+    let (score, err, svg) = vis::vis_with(&input, &output, turn, &options);
+    Ret { score, err, svg }
+}
+
+// Per-cycle counts of one case as a chart to show next to `vis`, with a
+// cursor on the cycle of `turn`.
+#[wasm_bindgen]
+pub fn vis_timeline(
+    _input: String,
+    _output: String,
+    case: usize,
+    turn: usize,
+    theme: String,
+) -> Ret {
+    let theme = match find_theme(&theme) {
+        Ok(theme) => theme,
+        Err(err) => return err,
+    };
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    let (score, err, svg) = vis::vis_timeline(&input, &output, case, turn, &theme);
+    Ret { score, err, svg }
+}
+
+// History of site (i, j) over the cycles of a case, one line per cycle.
+#[wasm_bindgen]
+pub fn site_history(_input: String, _output: String, case: usize, i: usize, j: usize) -> TextRet {
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    match timeline::site_history(&input, &output, case, i, j) {
+        Ok(history) => TextRet {
+            err: "".to_string(),
+            text: history.to_string(),
+        },
+        Err(err) => TextRet {
+            err,
+            text: "".to_string(),
+        },
+    }
+}
+
+// `site_history` as a strip chart, with a cursor on the cycle of `turn`.
+#[wasm_bindgen]
+pub fn vis_site_history(
+    _input: String,
+    _output: String,
+    case: usize,
+    i: usize,
+    j: usize,
+    turn: usize,
+    theme: String,
+) -> Ret {
+    let theme = match find_theme(&theme) {
+        Ok(theme) => theme,
+        Err(err) => return err,
+    };
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    let (score, err, svg) = vis::vis_site_history(&input, &output, case, (i, j), turn, &theme);
+    Ret { score, err, svg }
+}
+
+// One tile per case coloured by its outcome.
+#[wasm_bindgen]
+pub fn vis_summary(_input: String, _output: String, theme: String) -> Ret {
+    let theme = match find_theme(&theme) {
+        Ok(theme) => theme,
+        Err(err) => return err,
+    };
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    let (score, err, svg) = vis::vis_summary(&input, &output, &theme);
+    Ret { score, err, svg }
+}
+
+// How often each site was hit over all cases and cycles, for one of the
+// aggregates of `list_heatmaps`.
+#[wasm_bindgen]
+pub fn vis_heatmap(_input: String, _output: String, aggregate: String, theme: String) -> Ret {
+    let theme = match find_theme(&theme) {
+        Ok(theme) => theme,
+        Err(err) => return err,
+    };
+    let aggregate = match Aggregate::by_name(&aggregate) {
+        Some(aggregate) => aggregate,
+        None => {
+            return Ret {
+                score: 0,
+                err: format!("unknown heatmap: {}", aggregate),
+                svg: "".to_string(),
+            }
+        }
+    };
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    let (score, err, svg) = vis::vis_heatmap(&input, &output, aggregate, &theme);
+    Ret { score, err, svg }
+}
+
+// Names of the heatmap aggregates, one per line.
+#[wasm_bindgen]
+pub fn list_heatmaps() -> String {
+    Aggregate::ALL
+        .iter()
+        .map(|aggregate| aggregate.name())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Names of the colour themes, one per line.
+#[wasm_bindgen]
+pub fn list_themes() -> String {
+    Theme::NAMES.join("\n")
+}
+
+fn find_theme(name: &str) -> Result<Theme, Ret> {
+    Theme::by_name(name).ok_or_else(|| Ret {
+        score: 0,
+        err: format!("unknown theme: {}", name),
+        svg: "".to_string(),
+    })
+}
+
+#[wasm_bindgen]
+pub fn get_max_turn(_input: String, _output: String) -> usize {
+    let input = util::parse_input(&_input);
+    //let output = util::parse_output(&_output);
+    input.c * 2 + 1
+}
+
+#[wasm_bindgen]
+pub fn vis_union_find(
+    _input: String,
+    _output: String,
+    case: usize,
+    turn: usize,
+    step: usize,
+    theme: String,
+) -> Ret {
+    let theme = match find_theme(&theme) {
+        Ok(theme) => theme,
+        Err(err) => return err,
+    };
+    let input = util::parse_input(&_input);
+    let output = util::parse_output(&_output, input.c, input.t);
+    let (score, err, svg) = util::vis_union_find(&input, &output, case, turn, step, &theme);
+    Ret { score, err, svg }
+}
+
+// Number of Union-Find growth steps of the case; 0 when the case is out of
+// range, which `vis_union_find` reports as its error.
+#[wasm_bindgen]
+pub fn get_max_union_find_step(_input: String, case: usize) -> usize {
+    let input = util::parse_input(&_input);
+    util::get_max_union_find_step(&input, case).unwrap_or(0)
+}
+
+// Names of the built-in decoders, one per line.
+#[wasm_bindgen]
+pub fn list_decoders() -> String {
+    decoder::decoders()
+        .iter()
+        .map(|decoder| decoder.name())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct TextRet {
+    pub err: String,
+    pub text: String,
+}
+
+// Runs the named decoder on every case and returns its output file.
+#[wasm_bindgen]
+pub fn run_decoder(_input: String, name: String) -> TextRet {
+    let input = util::parse_input(&_input);
+    match decoder::find(&name) {
+        Some(decoder) => TextRet {
+            err: "".to_string(),
+            text: decoder::run(decoder.as_ref(), &input).to_string(),
+        },
+        None => TextRet {
+            err: format!("unknown decoder: {}", name),
+            text: "".to_string(),
+        },
+    }
+}
+
+// Stim circuit of the code and noise model of an instance, as a memory
+// experiment for the logical named by `memory` ("z" or "x").
+#[wasm_bindgen]
+pub fn to_stim(_input: String, memory: String) -> TextRet {
+    let input = util::parse_input(&_input);
+    match stim::Memory::by_name(&memory) {
+        Some(memory) => TextRet {
+            err: "".to_string(),
+            text: stim::to_stim(&input, memory),
+        },
+        None => TextRet {
+            err: format!("unknown memory: {}", memory),
+            text: "".to_string(),
+        },
+    }
+}
+
+// Detector error model of an instance in Stim's DEM format.
+#[wasm_bindgen]
+pub fn to_dem(_input: String) -> String {
+    let input = util::parse_input(&_input);
+    dem::to_dem(&input)
+}
@@ -0,0 +1,95 @@
+use rust::frame::Frame;
+use rust::input::parse_input;
+use rust::output::parse_output;
+use rust::theme::Theme;
+use rust::vis::{vis, vis_animation};
+
+// Values of every `name="..."` attribute in document order.
+fn attributes(svg: &str, name: &str) -> Vec<String> {
+    let key = format!(" {}=\"", name);
+    svg.split(&key)
+        .skip(1)
+        .map(|rest| rest[..rest.find('"').unwrap()].to_string())
+        .collect()
+}
+
+#[test]
+fn animation_shows_each_turn_in_its_slot() {
+    // one case of two cycles with an X error at (2, 0) in cycle 0
+    let input = parse_input("3 2 0 0 1\n1\n0 0 2 0\n0\n0\n");
+    let output = parse_output("", input.c, input.t);
+    let (_, err, svg) = vis_animation(&input, &output, 0, 0.5, &Theme::light());
+    assert_eq!(err, "");
+
+    // the layers below and above the qubits of 2c + 2 turns
+    let turns = input.c * 2 + 2;
+    assert_eq!(svg.matches("class=\"turn\"").count(), 2 * turns);
+    let values = attributes(&svg, "values");
+    let key_times = attributes(&svg, "keyTimes");
+    assert_eq!(values.len(), 2 * turns);
+    assert_eq!(key_times.len(), 2 * turns);
+    let at = |turn: usize| format!("{}", turn as f64 / turns as f64);
+    for turn in 0..turns {
+        let (expected_values, expected_key_times) = if turn == 0 {
+            ("visible;hidden".to_string(), format!("0;{}", at(1)))
+        } else if turn + 1 == turns {
+            ("hidden;visible".to_string(), format!("0;{}", at(turn)))
+        } else {
+            (
+                "hidden;visible;hidden".to_string(),
+                format!("0;{};{}", at(turn), at(turn + 1)),
+            )
+        };
+        for layer in [turn, turns + turn] {
+            assert_eq!(values[layer], expected_values, "turn {}", turn);
+            assert_eq!(key_times[layer], expected_key_times, "turn {}", turn);
+        }
+    }
+    assert!(attributes(&svg, "dur").iter().all(|dur| dur == "3000ms"));
+    // only the first turn shows without SMIL
+    let visibility = attributes(&svg, "visibility");
+    assert_eq!(visibility.len(), 2 * turns);
+    assert_eq!(
+        visibility.iter().filter(|v| *v == "visible").count(),
+        2,
+        "{:?}",
+        visibility
+    );
+
+    let (_, err, _) = vis_animation(&input, &output, 1, 0.5, &Theme::light());
+    assert_eq!(err, "case 1 is out of range");
+    let (_, err, _) = vis_animation(&input, &output, 0, 0.0, &Theme::light());
+    assert_eq!(err, "seconds per turn must be positive, got 0");
+}
+
+#[test]
+fn last_turn_sweeps_once_the_total_score_reaches_5000() {
+    // the X error at (2, 0) of case 0 is never corrected, so the case keeps a
+    // syndrome; case 1 of the second input is clean and brings the total
+    // score over 5000
+    let one = parse_input("3 2 0 0 1\n1\n0 0 2 0\n0\n0\n");
+    let two = parse_input("3 2 0 0 2\n1\n0 0 2 0\n0\n0\n");
+    let last = one.c * 2 + 1;
+    let residuals = |svg: &str| svg.matches("box residual x").count();
+
+    // below 5000 the last turn shows the final measurement again
+    let output = parse_output("", one.c, one.t);
+    let (score, _, measured) = vis(&one, &output, last - 1);
+    assert!(score < 5000, "{}", score);
+    let (_, _, shown) = vis(&one, &output, last);
+    assert_eq!(shown, measured);
+    assert_eq!(residuals(&shown), 1);
+
+    // from 5000 it shows the residual of case 0 swept to row 0, syndrome
+    // or not
+    let output = parse_output("", two.c, two.t);
+    let (score, _, measured) = vis(&two, &output, last - 1);
+    assert!(score >= 5000, "{}", score);
+    let (_, _, shown) = vis(&two, &output, last);
+    assert_ne!(shown, measured);
+    let mut swept = Frame::new(two.d);
+    swept.x.flip(2, 0);
+    swept.hakidashi();
+    assert!(!swept.x.get(2, 0));
+    assert_eq!(residuals(&shown), swept.x.count_ones());
+}
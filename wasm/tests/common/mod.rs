@@ -0,0 +1,70 @@
+// Inputs shared by the tests of the views over cases and a reader of the
+// SVGs they draw; each test crate uses only some of them.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use rust::decoder::{self, union_find::UnionFind};
+use rust::gen::gen_instance;
+use rust::input::{Input, Instance};
+use rust::output::Output;
+
+// Three small noisy cases and their union-find corrections.
+pub fn decoded() -> (Input, Output) {
+    let (d, c, e_d, e_m) = (5, 10, 0.03, 0.03);
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let testcase = (0..3)
+        .map(|_| gen_instance(&mut rng, d, c, e_d, e_m))
+        .collect();
+    let input = Input {
+        d,
+        c,
+        e_d,
+        e_m,
+        t: 3,
+        testcase,
+    };
+    let output = decoder::run(&UnionFind, &input);
+    (input, output)
+}
+
+// `t` cases of `c` cycles without any errors.
+pub fn quiet(d: usize, c: usize, t: usize) -> Input {
+    Input {
+        d,
+        c,
+        e_d: 0.0,
+        e_m: 0.0,
+        t,
+        testcase: vec![
+            Instance {
+                x_depolarizing_error: vec![vec![]; c],
+                z_depolarizing_error: vec![vec![]; c],
+                measure_error: vec![vec![]; c],
+            };
+            t
+        ],
+    }
+}
+
+// Tag and attributes of every element.
+pub fn elements(svg: &str) -> Vec<(String, HashMap<String, String>)> {
+    svg.split('<')
+        .skip(1)
+        .filter(|element| !element.starts_with('/'))
+        .map(|element| {
+            let element = &element[..element.find('>').unwrap()];
+            let (tag, mut rest) = element.split_once(' ').unwrap_or((element, ""));
+            let mut attributes = HashMap::new();
+            while let Some((key, value)) = rest.split_once("=\"") {
+                let end = value.find('"').unwrap();
+                attributes.insert(key.trim().to_string(), value[..end].to_string());
+                rest = &value[end + 1..];
+            }
+            (tag.to_string(), attributes)
+        })
+        .collect()
+}
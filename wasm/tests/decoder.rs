@@ -0,0 +1,266 @@
+mod common;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha20Rng;
+
+use rust::decoder;
+use rust::decoder::blossom::{max_weight_matching, min_weight_perfect_matching};
+use rust::input::{parse_input, Input};
+use rust::score::{calculate_score, residual};
+
+// Cheapest perfect matching of vertices `left`, by trying every partner of
+// the lowest one; None if there is none.
+fn brute_force_perfect(left: u32, cost: &[Vec<Option<i64>>]) -> Option<i64> {
+    if left == 0 {
+        return Some(0);
+    }
+    let u = left.trailing_zeros() as usize;
+    let rest = left & !(1 << u);
+    let mut best = None;
+    for v in 0..cost.len() {
+        if rest & (1 << v) == 0 {
+            continue;
+        }
+        if let (Some(w), Some(others)) = (cost[u][v], brute_force_perfect(rest & !(1 << v), cost)) {
+            best = Some(best.map_or(w + others, |b: i64| b.min(w + others)));
+        }
+    }
+    best
+}
+
+// Heaviest matching of vertices `left`, the lowest one either unmatched or
+// matched to each of its neighbours in turn.
+fn brute_force_max(left: u32, weight: &[Vec<Option<i64>>]) -> i64 {
+    if left == 0 {
+        return 0;
+    }
+    let u = left.trailing_zeros() as usize;
+    let rest = left & !(1 << u);
+    let mut best = brute_force_max(rest, weight);
+    for v in 0..weight.len() {
+        if rest & (1 << v) != 0 {
+            if let Some(w) = weight[u][v] {
+                best = best.max(w + brute_force_max(rest & !(1 << v), weight));
+            }
+        }
+    }
+    best
+}
+
+fn random_graph(rng: &mut ChaCha20Rng, n: usize, density: f64) -> Vec<(usize, usize, i64)> {
+    let mut edges = vec![];
+    for u in 0..n {
+        for v in u + 1..n {
+            if rng.gen_bool(density) {
+                edges.push((u, v, rng.gen_range(0, 20)));
+            }
+        }
+    }
+    edges
+}
+
+fn table(n: usize, edges: &[(usize, usize, i64)]) -> Vec<Vec<Option<i64>>> {
+    let mut table = vec![vec![None; n]; n];
+    for &(u, v, w) in edges {
+        table[u][v] = Some(w);
+        table[v][u] = Some(w);
+    }
+    table
+}
+
+fn check_mates(mate: &[Option<usize>]) {
+    for (v, m) in mate.iter().enumerate() {
+        if let Some(m) = *m {
+            assert_eq!(mate[m], Some(v));
+        }
+    }
+}
+
+#[test]
+fn perfect_matching_is_cheapest() {
+    // Small weights give many ties and tight edges, which exercises the jump
+    // start of the matcher as well as the blossoms.
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    for trial in 0..300 {
+        let n = 2 * rng.gen_range(1, 6);
+        let edges = random_graph(&mut rng, n, 0.6);
+        let cost = table(n, &edges);
+        let expected = brute_force_perfect((1 << n) - 1, &cost);
+        match min_weight_perfect_matching(n, &edges) {
+            Some(mate) => {
+                for (v, &m) in mate.iter().enumerate() {
+                    assert_eq!(mate[m], v, "trial {}", trial);
+                }
+                let total: i64 = (0..n)
+                    .filter(|&v| v < mate[v])
+                    .map(|v| cost[v][mate[v]].unwrap())
+                    .sum();
+                assert_eq!(Some(total), expected, "trial {}", trial);
+            }
+            None => assert_eq!(expected, None, "trial {}", trial),
+        }
+    }
+}
+
+#[test]
+fn perfect_matching_with_equal_weights() {
+    // Every edge of a 6-cycle with a chord is tight after the jump start, so
+    // its greedy pass has to pick among them.
+    let edges = vec![
+        (0, 1, 1),
+        (1, 2, 1),
+        (2, 3, 1),
+        (3, 4, 1),
+        (4, 5, 1),
+        (5, 0, 1),
+        (0, 3, 1),
+    ];
+    let mate = min_weight_perfect_matching(6, &edges).unwrap();
+    for (v, &m) in mate.iter().enumerate() {
+        assert_eq!(mate[m], v);
+        assert_ne!(m, v);
+    }
+    assert_eq!(
+        min_weight_perfect_matching(3, &[(0, 1, 1), (1, 2, 1)]),
+        None
+    );
+}
+
+#[test]
+fn max_weight_matching_is_heaviest() {
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+    for trial in 0..300 {
+        let n = rng.gen_range(1, 11);
+        let edges = random_graph(&mut rng, n, 0.5);
+        let weight = table(n, &edges);
+        let mate = max_weight_matching(n, &edges, false);
+        check_mates(&mate);
+        let total: i64 = (0..n)
+            .filter_map(|v| mate[v].filter(|&m| v < m).map(|m| weight[v][m].unwrap()))
+            .sum();
+        assert_eq!(
+            total,
+            brute_force_max((1 << n) - 1, &weight),
+            "trial {}",
+            trial
+        );
+    }
+}
+
+// Score of a single case with no faulty syndrome and both logicals right.
+const PERFECT: i64 = 10000;
+
+#[test]
+fn decoders_correct_single_errors() {
+    for decoder in decoder::decoders() {
+        let d = 5;
+        for cycle in [0, 3] {
+            for i in 0..d {
+                for j in 0..d {
+                    let mut input = common::quiet(d, 10, 1);
+                    input.e_d = 0.01;
+                    input.e_m = 0.01;
+                    if (i + j) % 2 == 0 {
+                        input.testcase[0].x_depolarizing_error[cycle].push((i, j));
+                        input.testcase[0].z_depolarizing_error[cycle].push((i, j));
+                    } else {
+                        input.testcase[0].measure_error[cycle].push((i, j));
+                    }
+                    let output = decoder::run(decoder.as_ref(), &input);
+                    assert_eq!(
+                        calculate_score(&input, &output),
+                        PERFECT,
+                        "{} at ({}, {}) in cycle {}",
+                        decoder.name(),
+                        i,
+                        j,
+                        cycle
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn decoders_handle_fewer_cycles_than_the_generator() {
+    // No measurement errors and fewer cycles than the generator keeps quiet.
+    let input = parse_input("5 3 0.1 0 1\n1\n0 0 2 2\n0\n0\n");
+    for decoder in decoder::decoders() {
+        let output = decoder::run(decoder.as_ref(), &input);
+        assert_eq!(
+            calculate_score(&input, &output),
+            PERFECT,
+            "{}",
+            decoder.name()
+        );
+    }
+}
+
+#[test]
+fn decoders_leave_unreachable_events() {
+    // Without noise the graphs have no edges, so no event can be matched.
+    let mut input = common::quiet(5, 3, 1);
+    input.testcase[0].x_depolarizing_error[0].push((2, 2));
+    for decoder in decoder::decoders() {
+        let output = decoder::run(decoder.as_ref(), &input);
+        assert!(
+            output.x_correction[0].iter().all(|cycle| cycle.is_empty()),
+            "{}",
+            decoder.name()
+        );
+    }
+}
+
+#[test]
+fn decoders_correct_low_noise() {
+    let mut rng = ChaCha20Rng::seed_from_u64(2);
+    for _ in 0..5 {
+        let instance = rust::gen::gen_instance(&mut rng, 9, 20, 0.002, 0.002);
+        let mut input = common::quiet(9, 20, 1);
+        input.e_d = 0.002;
+        input.e_m = 0.002;
+        input.testcase[0] = instance;
+        for decoder in decoder::decoders() {
+            let output = decoder::run(decoder.as_ref(), &input);
+            assert_eq!(
+                calculate_score(&input, &output),
+                PERFECT,
+                "{}",
+                decoder.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn decoders_leave_no_syndrome_on_noisy_input() {
+    // About seven X and seven Z errors per case: every defect that lasts
+    // through the quiet cycles has to be corrected, not sent to the time
+    // boundary.
+    let mut rng = ChaCha20Rng::seed_from_u64(3);
+    let (d, c, e_d, e_m, t) = (7, 20, 0.03, 0.03, 20);
+    let testcase = (0..t)
+        .map(|_| rust::gen::gen_instance(&mut rng, d, c, e_d, e_m))
+        .collect();
+    let input = Input {
+        d,
+        c,
+        e_d,
+        e_m,
+        t,
+        testcase,
+    };
+    for decoder in decoder::decoders() {
+        let output = decoder::run(decoder.as_ref(), &input);
+        for case in 0..t {
+            assert_eq!(
+                residual(&input, &output, case).faulty(),
+                0,
+                "{} in case {}",
+                decoder.name(),
+                case
+            );
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use rust::input::parse_input;
+use rust::output::parse_output;
+use rust::theme::Theme;
+use rust::vis::vis_diff;
+
+#[test]
+fn diff_shows_the_requested_case() {
+    // two cases of two cycles; only case 1 has an error, at (0, 0) in cycle
+    // 0, and only the second output corrects it
+    let input = parse_input("3 2 0 0 2\n1\n1 0 0 0\n0\n0\n");
+    let first = parse_output("0\n0\n", input.c, input.t);
+    let second = parse_output("1\n1 0 0 0\n0\n", input.c, input.t);
+    let theme = Theme::light();
+    // turn 1 shows the corrections of cycle 0
+    let (_, _, err, svg) = vis_diff(&input, &first, &second, 0, 1, &theme);
+    assert_eq!(err, "");
+    assert!(!svg.contains("class=\"diff\""));
+    let (_, _, err, svg) = vis_diff(&input, &first, &second, 1, 1, &theme);
+    assert_eq!(err, "");
+    assert!(svg.contains("class=\"diff\""));
+    assert!(svg.contains("box correction second"));
+
+    let (_, _, err, svg) = vis_diff(&input, &first, &second, 2, 1, &theme);
+    assert_eq!(err, "case 2 is out of range");
+    assert_eq!(svg, "");
+}
@@ -0,0 +1,48 @@
+use rust::decoder::{self, union_find::UnionFind};
+use rust::equivalence::{compare, Relation};
+use rust::gen::gen;
+use rust::minimize::minimize;
+
+#[test]
+fn minimized_output_is_equivalent() {
+    let input = gen(800);
+    let output = decoder::run(&UnionFind, &input);
+    let minimized = minimize(&input, &output).output;
+    assert!(compare(&input, &output, &minimized)
+        .iter()
+        .all(|&relation| relation == Relation::Equivalent));
+}
+
+#[test]
+fn logical_operators_are_told_apart() {
+    let input = gen(800);
+    let output = decoder::run(&UnionFind, &input);
+    let last = input.c - 1;
+
+    // X on every data qubit of row 0, Z on every data qubit of column 0
+    let mut x = output.clone();
+    for j in (0..input.d).step_by(2) {
+        x.x_correction[0][last].push((0, j));
+    }
+    assert_eq!(
+        compare(&input, &output, &x)[0],
+        Relation::Logical { x: true, z: false }
+    );
+    let mut xz = x.clone();
+    for i in (0..input.d).step_by(2) {
+        xz.z_correction[0][last].push((i, 0));
+    }
+    assert_eq!(
+        compare(&input, &output, &xz)[0],
+        Relation::Logical { x: true, z: true }
+    );
+    assert_eq!(
+        compare(&input, &x, &xz)[0],
+        Relation::Logical { x: false, z: true }
+    );
+
+    // a single Z in the bulk is seen by its two Z ancillas
+    let mut flipped = output.clone();
+    flipped.z_correction[0][last].push((2, 2));
+    assert_eq!(compare(&input, &output, &flipped)[0], Relation::Syndrome(2));
+}
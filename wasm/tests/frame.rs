@@ -0,0 +1,174 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha20Rng;
+
+use rust::frame::{BitGrid, Frame};
+
+// distances around the word boundaries of a BitGrid row
+const DISTANCES: [usize; 7] = [3, 5, 63, 65, 67, 127, 129];
+
+// X and Z errors on random data qubits.
+fn random_frame(rng: &mut impl Rng, d: usize) -> Frame {
+    let mut frame = Frame::new(d);
+    for i in 0..d {
+        for j in 0..d {
+            if (i + j) % 2 == 0 {
+                if rng.gen_bool(0.3) {
+                    frame.x.flip(i, j);
+                }
+                if rng.gen_bool(0.3) {
+                    frame.z.flip(i, j);
+                }
+            }
+        }
+    }
+    frame
+}
+
+// Parity of the neighbouring X errors at odd j and Z errors at even j, one
+// ancilla at a time.
+fn naive_syndrome(x: &[Vec<usize>], z: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let d = x.len();
+    let mut ret = vec![vec![0; d]; d];
+    for i in 0..d {
+        for j in 0..d {
+            if (i + j) % 2 == 0 {
+                continue;
+            }
+            let errors = if j % 2 == 1 { x } else { z };
+            let mut parity = 0;
+            if i > 0 {
+                parity ^= errors[i - 1][j];
+            }
+            if i + 1 < d {
+                parity ^= errors[i + 1][j];
+            }
+            if j > 0 {
+                parity ^= errors[i][j - 1];
+            }
+            if j + 1 < d {
+                parity ^= errors[i][j + 1];
+            }
+            ret[i][j] = parity;
+        }
+    }
+    ret
+}
+
+// Sweeps one site at a time: X errors of the even rows up to row 0, Z errors
+// of the even columns left to column 0.
+fn naive_hakidashi(x: &mut [Vec<usize>], z: &mut [Vec<usize>]) {
+    let d = x.len();
+    for i in (1..d.saturating_sub(1)).rev().step_by(2) {
+        for j in (0..d).step_by(2) {
+            if x[i + 1][j] == 1 {
+                x[i + 1][j] ^= 1;
+                if j != 0 {
+                    x[i][j - 1] ^= 1;
+                }
+                if j != d - 1 {
+                    x[i][j + 1] ^= 1;
+                }
+                x[i - 1][j] ^= 1;
+            }
+        }
+    }
+    for j in (1..d.saturating_sub(1)).rev().step_by(2) {
+        for i in (0..d).step_by(2) {
+            if z[i][j + 1] == 1 {
+                z[i][j + 1] ^= 1;
+                if i != 0 {
+                    z[i - 1][j] ^= 1;
+                }
+                if i != d - 1 {
+                    z[i + 1][j] ^= 1;
+                }
+                z[i][j - 1] ^= 1;
+            }
+        }
+    }
+}
+
+#[test]
+fn syndrome_matches_naive_grid() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    for d in DISTANCES {
+        for _ in 0..4 {
+            let frame = random_frame(&mut rng, d);
+            let expected = naive_syndrome(&frame.x.to_grid(), &frame.z.to_grid());
+            assert_eq!(frame.syndrome().to_grid(), expected, "d {}", d);
+            let faulty: usize = expected.iter().flatten().sum();
+            assert_eq!(frame.faulty(), faulty, "d {}", d);
+        }
+    }
+}
+
+#[test]
+fn hakidashi_matches_naive_grid() {
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+    for d in DISTANCES {
+        for _ in 0..4 {
+            let mut frame = random_frame(&mut rng, d);
+            let mut x = frame.x.to_grid();
+            let mut z = frame.z.to_grid();
+            naive_hakidashi(&mut x, &mut z);
+            frame.hakidashi();
+            assert_eq!(frame.x, BitGrid::from_grid(&x), "d {}", d);
+            assert_eq!(frame.z, BitGrid::from_grid(&z), "d {}", d);
+        }
+    }
+}
+
+#[test]
+fn logical_matches_hakidashi_without_syndrome() {
+    let mut rng = ChaCha20Rng::seed_from_u64(2);
+    for d in DISTANCES {
+        for flips in [(false, false), (true, false), (false, true), (true, true)] {
+            // random stabilizers: X on the data qubits around even-j ancillas,
+            // Z around odd-j ones
+            let mut frame = Frame::new(d);
+            for i in 0..d {
+                for j in 0..d {
+                    if (i + j) % 2 == 0 || !rng.gen_bool(0.5) {
+                        continue;
+                    }
+                    let grid = if j % 2 == 0 {
+                        &mut frame.x
+                    } else {
+                        &mut frame.z
+                    };
+                    if i > 0 {
+                        grid.flip(i - 1, j);
+                    }
+                    if i + 1 < d {
+                        grid.flip(i + 1, j);
+                    }
+                    if j > 0 {
+                        grid.flip(i, j - 1);
+                    }
+                    if j + 1 < d {
+                        grid.flip(i, j + 1);
+                    }
+                }
+            }
+            // the logical X along row 0 and the logical Z along column 0
+            for k in (0..d).step_by(2) {
+                if flips.0 {
+                    frame.x.flip(0, k);
+                }
+                if flips.1 {
+                    frame.z.flip(k, 0);
+                }
+            }
+            assert_eq!(frame.faulty(), 0, "d {}", d);
+            assert_eq!(frame.logical(), flips, "d {}", d);
+            let mut swept = frame.clone();
+            swept.hakidashi();
+            assert_eq!(
+                frame.logical(),
+                (swept.x.get(0, 0), swept.z.get(0, 0)),
+                "d {}",
+                d
+            );
+        }
+    }
+}
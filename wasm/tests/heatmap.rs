@@ -0,0 +1,98 @@
+mod common;
+
+use rust::heatmap::{heatmap, Aggregate};
+use rust::theme::Theme;
+use rust::vis::vis_heatmap;
+
+#[test]
+fn heatmaps_count_every_listed_site() {
+    let (input, output) = common::decoded();
+    for aggregate in Aggregate::ALL {
+        assert_eq!(Aggregate::by_name(aggregate.name()), Some(aggregate));
+        let counts = heatmap(&input, &output, aggregate);
+        let mut total = 0;
+        for case in 0..input.t {
+            let instance = &input.testcase[case];
+            for cycle in 0..input.c {
+                total += match aggregate {
+                    Aggregate::XErrors => instance.x_depolarizing_error[cycle].len(),
+                    Aggregate::ZErrors => instance.z_depolarizing_error[cycle].len(),
+                    Aggregate::MeasurementErrors => instance.measure_error[cycle].len(),
+                    Aggregate::XCorrections => output.x_correction[case][cycle].len(),
+                    Aggregate::ZCorrections => output.z_correction[case][cycle].len(),
+                };
+            }
+        }
+        assert!(total > 0, "{}", aggregate.name());
+        assert_eq!(counts.iter().flatten().sum::<usize>(), total);
+        // nothing outside the sites the aggregate counts
+        for (i, row) in counts.iter().enumerate() {
+            for (j, &count) in row.iter().enumerate() {
+                if !aggregate.counts_site(i, j) {
+                    assert_eq!(count, 0, "{} at ({}, {})", aggregate.name(), i, j);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn heatmaps_shade_one_cell_per_site_by_its_count() {
+    let (input, output) = common::decoded();
+    let s = 1000.0 / input.d as f64;
+    let site = |v: &str| (v.parse::<f64>().unwrap() / s).round() as usize;
+    for aggregate in Aggregate::ALL {
+        let counts = heatmap(&input, &output, aggregate);
+        let (_, err, svg) = vis_heatmap(&input, &output, aggregate, &Theme::light());
+        assert_eq!(err, "");
+
+        // a count on every site the aggregate counts
+        let labels: Vec<&str> = svg
+            .split("<text class=\"count\"")
+            .skip(1)
+            .map(|text| text[text.find('>').unwrap() + 1..text.find('<').unwrap()].trim())
+            .collect();
+        let sites: Vec<(usize, usize)> = (0..input.d)
+            .flat_map(|i| (0..input.d).map(move |j| (i, j)))
+            .filter(|&(i, j)| aggregate.counts_site(i, j))
+            .collect();
+        assert_eq!(labels.len(), sites.len(), "{}", aggregate.name());
+        for (label, &(i, j)) in labels.iter().zip(&sites) {
+            assert_eq!(label.parse::<usize>().unwrap(), counts[i][j]);
+        }
+
+        // a shaded cell on every site hit, the most hit ones fully opaque
+        let max = sites.iter().map(|&(i, j)| counts[i][j]).max().unwrap();
+        let mut shades = vec![];
+        for (tag, attributes) in common::elements(&svg) {
+            if tag != "rect" || !attributes["class"].starts_with("heat ") {
+                continue;
+            }
+            let (i, j) = (site(&attributes["y"]), site(&attributes["x"]));
+            let opacity: f64 = attributes["fill-opacity"].parse().unwrap();
+            assert!(aggregate.counts_site(i, j) && counts[i][j] > 0);
+            assert_eq!(opacity == 1.0, counts[i][j] == max, "{}", aggregate.name());
+            shades.push((counts[i][j], opacity));
+        }
+        let hit = sites.iter().filter(|&&(i, j)| counts[i][j] > 0).count();
+        assert_eq!(shades.len(), hit, "{}", aggregate.name());
+        shades.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for pair in shades.windows(2) {
+            assert!(pair[0].1 <= pair[1].1, "{:?}", pair);
+        }
+    }
+}
+
+#[test]
+fn heatmap_names_resolve_only_to_listed_aggregates() {
+    // the export looks the name up here and reports a miss as unknown
+    for name in ["y-errors", "", "X-Errors", "x_errors"] {
+        assert_eq!(Aggregate::by_name(name), None, "{:?}", name);
+    }
+    let (input, output) = common::decoded();
+    let aggregate = Aggregate::by_name("x-errors").unwrap();
+    assert_eq!(aggregate, Aggregate::XErrors);
+    let (_, err, svg) = vis_heatmap(&input, &output, aggregate, &Theme::light());
+    assert_eq!(err, "");
+    assert!(svg.starts_with("<svg"));
+}
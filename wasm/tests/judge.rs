@@ -0,0 +1,41 @@
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+// An input with one case of two noiseless cycles, written to a file of its own.
+fn input_file(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("judge-{}-{}.txt", name, std::process::id()));
+    std::fs::write(&path, "3 2 0 0 1\n0\n0\n0\n").unwrap();
+    path
+}
+
+#[test]
+fn judge_scores_an_answering_solver() {
+    let path = input_file("answer");
+    // reads the header, then answers every cycle with no corrections
+    let solver = "read header; for cycle in 1 2; do read k; echo 0; echo 0; done";
+    let result = Command::new(env!("CARGO_BIN_EXE_judge"))
+        .arg(&path)
+        .args(["sh", "-c", solver])
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("Score = 10000"));
+}
+
+#[test]
+fn judge_stops_a_solver_that_never_answers() {
+    let path = input_file("silent");
+    let start = Instant::now();
+    let result = Command::new(env!("CARGO_BIN_EXE_judge"))
+        .arg(&path)
+        .args(["--timeout", "0.2", "sh", "-c", "cat > /dev/null"])
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(!result.status.success());
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("case 0 cycle 0: the solver did not answer in time"));
+    assert!(stderr.contains("Score = 0"));
+}
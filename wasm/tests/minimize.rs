@@ -0,0 +1,73 @@
+use rust::decoder::{self, union_find::UnionFind};
+use rust::frame::Frame;
+use rust::gen::gen;
+use rust::input::Input;
+use rust::minimize::minimize;
+use rust::output::Output;
+use rust::score::calculate_score;
+use rust::sdk::{Lattice, OutputBuilder};
+
+fn corrections(input: &Input, output: &Output, case: usize) -> Frame {
+    let mut frame = Frame::new(input.d);
+    for cycle in 0..input.c {
+        frame.x.apply(&output.x_correction[case][cycle]);
+        frame.z.apply(&output.z_correction[case][cycle]);
+    }
+    frame
+}
+
+#[test]
+fn minimize_keeps_syndrome_and_logical_class() {
+    for seed in [200, 800] {
+        let input = gen(seed);
+        let output = decoder::run(&UnionFind, &input);
+        let minimized = minimize(&input, &output);
+        assert_eq!(
+            calculate_score(&input, &minimized.output),
+            calculate_score(&input, &output),
+            "seed {}",
+            seed
+        );
+        for case in 0..input.t {
+            let before = corrections(&input, &output, case);
+            let after = corrections(&input, &minimized.output, case);
+            assert_eq!(
+                after.syndrome(),
+                before.syndrome(),
+                "seed {} case {}",
+                seed,
+                case
+            );
+            assert_eq!(
+                after.logical(),
+                before.logical(),
+                "seed {} case {}",
+                seed,
+                case
+            );
+            assert!(minimized.after[case] <= minimized.before[case]);
+        }
+        let again = minimize(&input, &minimized.output);
+        assert_eq!(again.after, minimized.after, "seed {}", seed);
+    }
+}
+
+#[test]
+fn minimize_cancels_stabilizers() {
+    let input = gen(800);
+    let lattice = Lattice::of(&input);
+    let q = |i, j| lattice.data_qubit(i, j).unwrap();
+    // X on the four qubits around the Z ancilla at (3, 2) is a stabilizer,
+    // here spread over two cycles
+    let mut builder = OutputBuilder::for_input(&input);
+    builder
+        .correct_x(0, 0, q(2, 2))
+        .correct_x(0, 0, q(3, 1))
+        .correct_x(0, 1, q(4, 2))
+        .correct_x(0, 1, q(3, 3));
+    let output = builder.build();
+    let minimized = minimize(&input, &output);
+    assert_eq!(minimized.before[0], 4);
+    assert_eq!(minimized.after[0], 0);
+    assert_eq!(minimized.removed(), 4);
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use rust::score::{case_score, outcome, residual, Outcome};
+use rust::sdk::OutputBuilder;
+use rust::theme::Theme;
+use rust::vis::vis_summary;
+
+#[test]
+fn outcomes_follow_the_score() {
+    let input = common::quiet(5, 2, 4);
+    let mut output = OutputBuilder::for_input(&input).build();
+    let last = input.c - 1;
+    // leave case 0 alone, flip the X logical of case 1, both logicals of
+    // case 2 and leave a syndrome in case 3
+    for case in [1, 2] {
+        for j in (0..input.d).step_by(2) {
+            output.x_correction[case][last].push((0, j));
+        }
+    }
+    for i in (0..input.d).step_by(2) {
+        output.z_correction[2][last].push((i, 0));
+    }
+    output.x_correction[3][last].push((2, 2));
+
+    let outcomes: Vec<Outcome> = (0..4)
+        .map(|case| outcome(&residual(&input, &output, case)))
+        .collect();
+    assert_eq!(outcomes[0], Outcome::Correct);
+    assert_eq!(outcomes[1], Outcome::OneFlipped);
+    assert_eq!(outcomes[2], Outcome::BothFlipped);
+    assert_eq!(outcomes[3], Outcome::Faulty(2));
+
+    let scores: Vec<i64> = (0..4)
+        .map(|case| case_score(&residual(&input, &output, case)))
+        .collect();
+    assert_eq!(scores, vec![10000, 7000, 5000, 4955]);
+
+    let (_, err, svg) = vis_summary(&input, &output, &Theme::default());
+    assert!(err.is_empty());
+    assert_eq!(svg.matches("data-case=").count(), input.t);
+    assert!(svg.contains("class=\"tile faulty\" data-case=\"3\""));
+}
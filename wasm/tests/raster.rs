@@ -0,0 +1,68 @@
+#![cfg(feature = "png")]
+
+mod common;
+
+use resvg::tiny_skia::Pixmap;
+use resvg::usvg::fontdb;
+use rust::raster::Rasterizer;
+use rust::vis::{vis, vis_with, VisOptions};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Width and height from the IHDR chunk, which follows the signature.
+fn dimensions(png: &[u8]) -> (u32, u32) {
+    let word = |k: usize| u32::from_be_bytes(png[k..k + 4].try_into().unwrap());
+    (word(16), word(20))
+}
+
+const TEXT: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="60"><text x="10" y="45" font-family="sans-serif" font-size="40">case 0</text></svg>"#;
+
+fn inked(png: &[u8]) -> usize {
+    let pixmap = Pixmap::decode_png(png).unwrap();
+    pixmap
+        .pixels()
+        .iter()
+        .filter(|pixel| pixel.red() < 128)
+        .count()
+}
+
+#[test]
+fn vis_renders_to_png_of_its_size() {
+    let (input, output) = common::decoded();
+    let rasterizer = Rasterizer::new();
+    let (_, err, svg) = vis(&input, &output, 3);
+    assert_eq!(err, "");
+    let png = rasterizer.to_png(&svg, 1.0).unwrap();
+    assert_eq!(png[..8], SIGNATURE);
+    assert_eq!(dimensions(&png), (1010, 1010));
+
+    // the panels widen and heighten the image, scaled with it
+    let options = VisOptions {
+        legend: true,
+        hud: true,
+        ..VisOptions::default()
+    };
+    let (_, err, svg) = vis_with(&input, &output, 3, &options);
+    assert_eq!(err, "");
+    let png = rasterizer.to_png(&svg, 0.5).unwrap();
+    assert_eq!(png[..8], SIGNATURE);
+    assert_eq!(dimensions(&png), (725, 530));
+}
+
+#[test]
+fn text_falls_back_to_an_installed_font() {
+    // without any font the text is left out rather than failing
+    let empty = Rasterizer::with_fonts(fontdb::Database::new());
+    let png = empty.to_png(TEXT, 1.0).unwrap();
+    assert_eq!(dimensions(&png), (200, 60));
+    assert_eq!(inked(&png), 0);
+
+    let mut fonts = fontdb::Database::new();
+    fonts.load_system_fonts();
+    if fonts.is_empty() {
+        return;
+    }
+    // sans-serif resolves to some installed face even without Arial
+    let png = Rasterizer::new().to_png(TEXT, 1.0).unwrap();
+    assert!(inked(&png) > 0);
+}
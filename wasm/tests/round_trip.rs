@@ -0,0 +1,68 @@
+use rust::decoder::{self, union_find::UnionFind};
+use rust::gen::gen;
+use rust::input::parse_input;
+use rust::output::parse_output;
+use rust::score::calculate_score;
+use rust::sdk::{Lattice, OutputBuilder};
+
+#[test]
+fn input_round_trip() {
+    // seeds above 100000 give the inputs with 100 cases
+    for seed in [0, 200, 400, 600, 800, 100001] {
+        let input = gen(seed);
+        let written = input.to_string();
+        let parsed = parse_input(&written);
+        assert_eq!(parsed, input, "seed {}", seed);
+        assert_eq!(parsed.to_string(), written, "seed {}", seed);
+    }
+}
+
+#[test]
+fn output_round_trip() {
+    for seed in [0, 800] {
+        let input = gen(seed);
+        let output = decoder::run(&UnionFind, &input);
+        let written = output.to_string();
+        let parsed = parse_output(&written, input.c, input.t);
+        assert_eq!(parsed, output, "seed {}", seed);
+        assert_eq!(parsed.to_string(), written, "seed {}", seed);
+    }
+}
+
+#[test]
+fn empty_output_round_trip() {
+    let output = parse_output("", 3, 2);
+    assert_eq!(output.to_string(), "0\n0\n");
+    assert_eq!(parse_output(&output.to_string(), 3, 2), output);
+}
+
+#[test]
+fn canonical_output() {
+    let input = gen(800);
+    let lattice = Lattice::of(&input);
+    let q = |i, j| lattice.data_qubit(i, j).unwrap();
+    let mut builder = OutputBuilder::for_input(&input);
+    for (i, j) in [(4, 2), (0, 0), (4, 2), (2, 2), (0, 0), (0, 0)] {
+        builder.correct_x(0, 3, q(i, j));
+    }
+    builder
+        .correct_z(0, 7, q(1, 1))
+        .correct_z(0, 7, q(1, 1))
+        .correct_z(0, 9, q(3, 5))
+        .correct_z(0, 9, q(1, 1));
+    let output = builder.build();
+
+    let canonical = output.canonical();
+    assert_eq!(canonical.x_correction[0][3], vec![(0, 0), (2, 2)]);
+    assert!(canonical.z_correction[0][7].is_empty());
+    assert_eq!(canonical.z_correction[0][9], vec![(1, 1), (3, 5)]);
+    assert_eq!(canonical.canonical(), canonical);
+    assert_eq!(
+        calculate_score(&input, &canonical),
+        calculate_score(&input, &output)
+    );
+
+    let written = output.to_canonical_string();
+    assert_eq!(written, canonical.to_string());
+    assert_eq!(parse_output(&written, input.c, input.t), canonical);
+}
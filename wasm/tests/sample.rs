@@ -0,0 +1,113 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use rust::decoder::{Corrections, Decoder};
+use rust::gen::gen_instance;
+use rust::sample::{sample, Estimate, SampleConfig};
+use rust::syndrome::Syndromes;
+
+// Leaves every error in place.
+struct Nothing;
+
+impl Decoder for Nothing {
+    fn name(&self) -> &'static str {
+        "nothing"
+    }
+
+    fn decode(&self, syndromes: &Syndromes) -> (Corrections, Corrections) {
+        (vec![vec![]; syndromes.c], vec![vec![]; syndromes.c])
+    }
+}
+
+fn config(e_d: f64, max_shots: usize, target: Option<f64>) -> SampleConfig {
+    SampleConfig {
+        d: 3,
+        c: 6,
+        e_d,
+        e_m: 0.0,
+        seed: 0,
+        max_shots,
+        target,
+        z: 1.96,
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+#[test]
+fn wilson_interval() {
+    let (lo, hi) = Estimate {
+        failures: 10,
+        shots: 100,
+    }
+    .wilson(1.96);
+    assert!(close(lo, 0.0552) && close(hi, 0.1744), "{} {}", lo, hi);
+
+    // without failures the interval still has a nonzero upper end
+    let (lo, hi) = Estimate {
+        failures: 0,
+        shots: 100,
+    }
+    .wilson(1.96);
+    assert_eq!(lo, 0.0);
+    assert!(close(hi, 1.96 * 1.96 / (100.0 + 1.96 * 1.96)), "{}", hi);
+
+    let (lo, hi) = Estimate {
+        failures: 0,
+        shots: 0,
+    }
+    .wilson(1.96);
+    assert_eq!((lo, hi), (0.0, 1.0));
+}
+
+#[test]
+fn relative_error_is_half_width_over_rate() {
+    let estimate = Estimate {
+        failures: 10,
+        shots: 100,
+    };
+    let (lo, hi) = estimate.wilson(1.96);
+    assert!(close(estimate.relative_error(1.96), (hi - lo) / 2.0 / 0.1));
+    // more shots at the same rate narrow the interval
+    let more = Estimate {
+        failures: 1000,
+        shots: 10000,
+    };
+    assert!(more.relative_error(1.96) < estimate.relative_error(1.96));
+    let none = Estimate {
+        failures: 0,
+        shots: 100,
+    };
+    assert_eq!(none.relative_error(1.96), f64::INFINITY);
+}
+
+#[test]
+fn sample_stops_at_a_batch_once_the_target_is_met() {
+    // nearly every case fails, so the first check already meets the target
+    let report = sample(&Nothing, &config(0.3, 1000, Some(0.5)));
+    assert_eq!(report.combined.shots, 100);
+    assert_eq!(report.x.shots, 100);
+    assert!(report.combined.failures > 0);
+
+    // without a target every shot is taken
+    let report = sample(&Nothing, &config(0.3, 250, None));
+    assert_eq!(report.combined.shots, 250);
+
+    // without failures the target is never met
+    let report = sample(&Nothing, &config(0.0, 250, Some(0.5)));
+    assert_eq!(report.combined.shots, 250);
+    assert_eq!(report.combined.failures, 0);
+}
+
+#[test]
+fn gen_instance_keeps_short_cases_quiet() {
+    // fewer cycles than the generator keeps quiet: no data errors at all
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let instance = gen_instance(&mut rng, 5, 3, 0.5, 0.5);
+    assert_eq!(instance.x_depolarizing_error.len(), 3);
+    assert!(instance.x_depolarizing_error.iter().all(|e| e.is_empty()));
+    assert!(instance.z_depolarizing_error.iter().all(|e| e.is_empty()));
+    assert!(instance.measure_error.iter().any(|e| !e.is_empty()));
+}
@@ -0,0 +1,116 @@
+use rust::frame::Frame;
+use rust::input::parse_input;
+use rust::output::parse_output;
+use rust::sdk::{DataQubit, Lattice, OutputBuilder, Site};
+use rust::syndrome::Syndromes;
+
+fn sorted<T: Into<(usize, usize)>>(sites: impl Iterator<Item = T>) -> Vec<(usize, usize)> {
+    let mut ret: Vec<(usize, usize)> = sites.map(Into::into).collect();
+    ret.sort();
+    ret
+}
+
+#[test]
+fn sites_partition_the_lattice() {
+    for d in [3, 5, 7] {
+        let lattice = Lattice::new(d);
+        assert_eq!(lattice.sites().count(), d * d);
+        assert_eq!(lattice.data_qubits().count(), (d * d).div_ceil(2));
+        assert_eq!(lattice.x_ancillas().count(), (d * d - 1) / 4);
+        assert_eq!(lattice.z_ancillas().count(), (d * d - 1) / 4);
+        assert!(lattice.x_ancillas().all(|a| a.j() % 2 == 1));
+        assert!(lattice.z_ancillas().all(|a| a.j() % 2 == 0));
+        assert!(lattice.site(d, 0).is_none());
+        assert!(matches!(lattice.site(0, 0), Some(Site::Data(_))));
+    }
+}
+
+#[test]
+fn neighbours_are_the_syndrome_of_a_single_error() {
+    for d in [3, 5, 7] {
+        let lattice = Lattice::new(d);
+        for q in lattice.data_qubits() {
+            let mut frame = Frame::new(d);
+            frame.x.flip(q.i(), q.j());
+            assert_eq!(frame.syndrome().ones(), sorted(lattice.x_neighbours(q)));
+            let mut frame = Frame::new(d);
+            frame.z.flip(q.i(), q.j());
+            assert_eq!(frame.syndrome().ones(), sorted(lattice.z_neighbours(q)));
+        }
+    }
+}
+
+#[test]
+fn stabilizers_leave_no_syndrome() {
+    // A check reads the qubits it is a neighbour of; the opposite Pauli on
+    // those qubits is a stabilizer, which no check sees and which does not
+    // flip a logical.
+    for d in [3, 5, 7] {
+        let lattice = Lattice::new(d);
+        for a in lattice.x_ancillas() {
+            let qubits: Vec<DataQubit> = lattice.x_stabilizer(a).collect();
+            assert!(qubits.len() == 3 || qubits.len() == 4);
+            assert!(qubits
+                .iter()
+                .all(|&q| lattice.x_neighbours(q).any(|b| b == a)));
+            let mut frame = Frame::new(d);
+            for q in &qubits {
+                frame.z.flip(q.i(), q.j());
+            }
+            assert_eq!(frame.syndrome().count_ones(), 0);
+            assert_eq!(frame.logical(), (false, false));
+        }
+        for a in lattice.z_ancillas() {
+            let qubits: Vec<DataQubit> = lattice.z_stabilizer(a).collect();
+            assert!(qubits.len() == 3 || qubits.len() == 4);
+            assert!(qubits
+                .iter()
+                .all(|&q| lattice.z_neighbours(q).any(|b| b == a)));
+            let mut frame = Frame::new(d);
+            for q in &qubits {
+                frame.x.flip(q.i(), q.j());
+            }
+            assert_eq!(frame.syndrome().count_ones(), 0);
+            assert_eq!(frame.logical(), (false, false));
+        }
+    }
+}
+
+#[test]
+fn measured_ancillas_follow_the_syndromes() {
+    // X error at (2, 2) in cycle 0, Z error at (0, 0) in cycle 1
+    let input = parse_input("3 2 0 0 1\n1\n0 0 2 2\n1\n0 1 0 0\n0\n");
+    let lattice = Lattice::of(&input);
+    let syndromes = Syndromes::observe(&input, 0);
+    let corner = lattice.data_qubit(2, 2).unwrap();
+    assert_eq!(
+        sorted(lattice.measured_x(&syndromes, 0).into_iter()),
+        sorted(lattice.x_neighbours(corner))
+    );
+    assert!(lattice.measured_z(&syndromes, 0).is_empty());
+    let origin = lattice.data_qubit(0, 0).unwrap();
+    assert_eq!(
+        sorted(lattice.measured_z(&syndromes, 1).into_iter()),
+        sorted(lattice.z_neighbours(origin))
+    );
+}
+
+#[test]
+fn builder_output_round_trips() {
+    let lattice = Lattice::new(3);
+    let mut builder = OutputBuilder::new(2, 2);
+    builder
+        .correct_x(0, 1, lattice.data_qubit(2, 0).unwrap())
+        .correct_z(1, 0, lattice.data_qubit(1, 1).unwrap());
+    let output = builder.build();
+    assert_eq!(
+        output.x_correction,
+        vec![vec![vec![], vec![(2, 0)]], vec![vec![], vec![]]]
+    );
+    assert_eq!(
+        output.z_correction,
+        vec![vec![vec![], vec![]], vec![vec![(1, 1)], vec![]]]
+    );
+    assert_eq!(parse_output(&output.to_string(), 2, 2), output);
+    assert!(lattice.data_qubit(0, 1).is_none());
+}
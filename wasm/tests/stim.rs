@@ -0,0 +1,468 @@
+mod common;
+
+use rand::prelude::*;
+use rand_chacha::ChaCha20Rng;
+
+use rust::dem::mechanisms;
+use rust::input::parse_input;
+use rust::stim::{to_stim, Memory};
+
+// One instruction of a circuit: its name, the argument in parentheses (if
+// any) and its targets.
+struct Instruction {
+    name: String,
+    argument: Option<String>,
+    targets: Vec<String>,
+}
+
+fn parse(circuit: &str) -> Vec<Instruction> {
+    circuit
+        .lines()
+        .map(|line| {
+            // the arguments may contain spaces, as in "DETECTOR(0, 1, 2)"
+            let (head, targets) = match line.split_once(')') {
+                Some((head, targets)) => (head, targets),
+                None => line.split_once(' ').unwrap_or((line, "")),
+            };
+            let (name, argument) = match head.split_once('(') {
+                Some((name, argument)) => (name, Some(argument.to_string())),
+                None => (head, None),
+            };
+            Instruction {
+                name: name.to_string(),
+                argument,
+                targets: targets.split_whitespace().map(String::from).collect(),
+            }
+        })
+        .collect()
+}
+
+fn qubit(target: &str) -> usize {
+    target.parse().unwrap()
+}
+
+// Record index of a "rec[-k]" target, with `count` records so far.
+fn record(target: &str, count: usize) -> usize {
+    let k: usize = target
+        .trim_start_matches("rec[-")
+        .trim_end_matches(']')
+        .parse()
+        .unwrap();
+    count - k
+}
+
+// Pauli product of an MPP target such as "Z0*Z6*Z12", as (x, z) bits.
+fn product(target: &str, n: usize) -> (Vec<bool>, Vec<bool>) {
+    let mut x = vec![false; n];
+    let mut z = vec![false; n];
+    for factor in target.split('*') {
+        let q = qubit(&factor[1..]);
+        match &factor[..1] {
+            "X" => x[q] = true,
+            "Z" => z[q] = true,
+            "Y" => {
+                x[q] = true;
+                z[q] = true;
+            }
+            _ => panic!("unknown Pauli {}", factor),
+        }
+    }
+    (x, z)
+}
+
+// Stabilizer tableau (Aaronson-Gottesman): rows 0..n are destabilizers,
+// rows n..2n stabilizers, each with a sign bit.
+struct Tableau {
+    n: usize,
+    x: Vec<Vec<bool>>,
+    z: Vec<Vec<bool>>,
+    r: Vec<bool>,
+}
+
+impl Tableau {
+    fn new(n: usize) -> Tableau {
+        let mut x = vec![vec![false; n]; 2 * n];
+        let mut z = vec![vec![false; n]; 2 * n];
+        for q in 0..n {
+            x[q][q] = true;
+            z[n + q][q] = true;
+        }
+        Tableau {
+            n,
+            x,
+            z,
+            r: vec![false; 2 * n],
+        }
+    }
+
+    fn h(&mut self, a: usize) {
+        for row in 0..2 * self.n {
+            self.r[row] ^= self.x[row][a] && self.z[row][a];
+            let (x, z) = (self.x[row][a], self.z[row][a]);
+            self.x[row][a] = z;
+            self.z[row][a] = x;
+        }
+    }
+
+    fn cx(&mut self, a: usize, b: usize) {
+        for row in 0..2 * self.n {
+            let (xa, za, xb, zb) = (
+                self.x[row][a],
+                self.z[row][a],
+                self.x[row][b],
+                self.z[row][b],
+            );
+            self.r[row] ^= xa && zb && (xb == za);
+            self.x[row][b] = xb ^ xa;
+            self.z[row][a] = za ^ zb;
+        }
+    }
+
+    fn anticommutes(&self, row: usize, x: &[bool], z: &[bool]) -> bool {
+        (0..self.n).fold(false, |acc, q| {
+            acc ^ (self.x[row][q] && z[q]) ^ (self.z[row][q] && x[q])
+        })
+    }
+
+    // Multiplies the Pauli (x, z, r) by row `i`, tracking the sign.
+    fn multiply(&self, x: &mut [bool], z: &mut [bool], r: &mut bool, i: usize) {
+        let mut phase = 2 * (*r as i32) + 2 * (self.r[i] as i32);
+        for q in 0..self.n {
+            let (x1, z1, x2, z2) = (
+                self.x[i][q] as i32,
+                self.z[i][q] as i32,
+                x[q] as i32,
+                z[q] as i32,
+            );
+            phase += match (x1, z1) {
+                (0, 0) => 0,
+                (1, 1) => z2 - x2,
+                (1, 0) => z2 * (2 * x2 - 1),
+                _ => x2 * (1 - 2 * z2),
+            };
+            x[q] ^= self.x[i][q];
+            z[q] ^= self.z[i][q];
+        }
+        *r = phase.rem_euclid(4) == 2;
+    }
+
+    fn row_multiply(&mut self, h: usize, i: usize) {
+        let (mut x, mut z, mut r) = (self.x[h].clone(), self.z[h].clone(), self.r[h]);
+        self.multiply(&mut x, &mut z, &mut r, i);
+        self.x[h] = x;
+        self.z[h] = z;
+        self.r[h] = r;
+    }
+
+    // Measures the Pauli product (x, z); returns the outcome and whether it
+    // was random.
+    fn measure(&mut self, x: &[bool], z: &[bool], rng: &mut impl Rng) -> (bool, bool) {
+        let n = self.n;
+        if let Some(p) = (n..2 * n).find(|&row| self.anticommutes(row, x, z)) {
+            for row in 0..2 * n {
+                if row != p && self.anticommutes(row, x, z) {
+                    self.row_multiply(row, p);
+                }
+            }
+            self.x[p - n] = self.x[p].clone();
+            self.z[p - n] = self.z[p].clone();
+            self.r[p - n] = self.r[p];
+            let outcome = rng.gen_bool(0.5);
+            self.x[p] = x.to_vec();
+            self.z[p] = z.to_vec();
+            self.r[p] = outcome;
+            (outcome, true)
+        } else {
+            let (mut sx, mut sz, mut sr) = (vec![false; n], vec![false; n], false);
+            for row in 0..n {
+                if self.anticommutes(row, x, z) {
+                    self.multiply(&mut sx, &mut sz, &mut sr, row + n);
+                }
+            }
+            (sr, false)
+        }
+    }
+
+    // Flips the signs of the rows that anticommute with the Pauli (x, z).
+    fn apply(&mut self, x: &[bool], z: &[bool]) {
+        for row in 0..2 * self.n {
+            if self.anticommutes(row, x, z) {
+                self.r[row] ^= true;
+            }
+        }
+    }
+}
+
+fn single(n: usize, q: usize) -> Vec<bool> {
+    let mut bits = vec![false; n];
+    bits[q] = true;
+    bits
+}
+
+// Detector and observable values of one noiseless shot, and how many
+// measurements were random.
+fn simulate(circuit: &str, rng: &mut impl Rng) -> (Vec<bool>, Vec<bool>, usize) {
+    let instructions = parse(circuit);
+    let n = instructions
+        .iter()
+        .filter(|ins| ins.name == "QUBIT_COORDS")
+        .count();
+    let none = vec![false; n];
+    let mut tableau = Tableau::new(n);
+    let mut records = vec![];
+    let mut detectors = vec![];
+    let mut observables = vec![false; 2];
+    let mut random = 0;
+    for ins in &instructions {
+        assert!(
+            ins.argument.is_none()
+                || ins.name.ends_with("_COORDS")
+                || ins.name == "DETECTOR"
+                || ins.name == "OBSERVABLE_INCLUDE",
+            "{} is noisy",
+            ins.name
+        );
+        match ins.name.as_str() {
+            "QUBIT_COORDS" | "TICK" => {}
+            "H" => ins.targets.iter().for_each(|t| tableau.h(qubit(t))),
+            "CX" => {
+                for pair in ins.targets.chunks(2) {
+                    tableau.cx(qubit(&pair[0]), qubit(&pair[1]));
+                }
+            }
+            "R" | "RX" | "MR" => {
+                for t in &ins.targets {
+                    let q = single(n, qubit(t));
+                    let (x, z) = if ins.name == "RX" {
+                        (q, none.clone())
+                    } else {
+                        (none.clone(), q)
+                    };
+                    let (outcome, was_random) = tableau.measure(&x, &z, rng);
+                    if ins.name == "MR" {
+                        records.push(outcome);
+                        random += was_random as usize;
+                    }
+                    if outcome {
+                        // flip back to +1 with the anticommuting Pauli
+                        tableau.apply(&z, &x);
+                    }
+                }
+            }
+            "MPP" => {
+                for t in &ins.targets {
+                    let (x, z) = product(t, n);
+                    let (outcome, was_random) = tableau.measure(&x, &z, rng);
+                    records.push(outcome);
+                    random += was_random as usize;
+                }
+            }
+            "DETECTOR" => detectors.push(
+                ins.targets
+                    .iter()
+                    .fold(false, |acc, t| acc ^ records[record(t, records.len())]),
+            ),
+            "OBSERVABLE_INCLUDE" => {
+                let k: usize = ins.argument.as_ref().unwrap().parse().unwrap();
+                for t in &ins.targets {
+                    observables[k] ^= records[record(t, records.len())];
+                }
+            }
+            name => panic!("unexpected instruction {}", name),
+        }
+    }
+    (detectors, observables, random)
+}
+
+#[test]
+fn noiseless_circuit_is_deterministic() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    for (d, c) in [(3, 1), (3, 4), (5, 3)] {
+        for memory in Memory::ALL {
+            let circuit = to_stim(&common::quiet(d, c, 1), memory);
+            let mut detectors = 0;
+            for shot in 0..8 {
+                let (values, observables, random) = simulate(&circuit, &mut rng);
+                // the projection round of the other basis is random
+                assert!(random > 0);
+                assert!(
+                    values.iter().all(|&v| !v),
+                    "d {} c {} memory {} shot {}",
+                    d,
+                    c,
+                    memory.name(),
+                    shot
+                );
+                assert!(observables.iter().all(|&v| !v));
+                detectors = values.len();
+            }
+            assert_eq!(detectors, (d * d / 2) * c);
+        }
+    }
+}
+
+#[test]
+fn circuit_observes_one_logical() {
+    let input = common::quiet(3, 2, 1);
+    let z = to_stim(&input, Memory::Z);
+    let x = to_stim(&input, Memory::X);
+    assert!(z.contains("\nR 0 2 4 6 8\n"));
+    assert!(x.contains("\nRX 0 2 4 6 8\n"));
+    assert_eq!(z.matches("OBSERVABLE_INCLUDE").count(), 1);
+    assert!(z.ends_with("MPP Z0*Z6\nOBSERVABLE_INCLUDE(0) rec[-1]\n"));
+    assert!(x.ends_with("MPP X0*X2\nOBSERVABLE_INCLUDE(1) rec[-1]\n"));
+}
+
+// A fault of a noisy instruction: the Pauli ("X", "Y" or "Z") it applies to
+// a DEPOLARIZE1 target, or the flip of an MR result ("M").
+struct Fault {
+    instruction: usize,
+    target: usize,
+    pauli: &'static str,
+}
+
+// Detectors and observables a fault flips, found by propagating it as a
+// Pauli frame through the circuit.
+fn signature(instructions: &[Instruction], fault: &Fault) -> (Vec<usize>, Vec<usize>) {
+    let n = instructions
+        .iter()
+        .filter(|ins| ins.name == "QUBIT_COORDS")
+        .count();
+    let mut x = vec![false; n];
+    let mut z = vec![false; n];
+    let mut records = vec![];
+    let mut detector = 0;
+    let mut detectors = vec![];
+    let mut observables = vec![];
+    for (index, ins) in instructions.iter().enumerate() {
+        let qubits: Vec<usize> = match ins.name.as_str() {
+            "DETECTOR" | "OBSERVABLE_INCLUDE" | "MPP" => vec![],
+            _ => ins.targets.iter().map(|t| qubit(t)).collect(),
+        };
+        let fault_on = |k: usize| index == fault.instruction && k == fault.target;
+        match ins.name.as_str() {
+            "QUBIT_COORDS" | "TICK" => {}
+            "DEPOLARIZE1" => {
+                for (k, &q) in qubits.iter().enumerate() {
+                    if fault_on(k) {
+                        x[q] ^= fault.pauli != "Z";
+                        z[q] ^= fault.pauli != "X";
+                    }
+                }
+            }
+            "R" | "RX" => {
+                for &q in &qubits {
+                    x[q] = false;
+                    z[q] = false;
+                }
+            }
+            "H" => {
+                for &q in &qubits {
+                    std::mem::swap(&mut x[q], &mut z[q]);
+                }
+            }
+            "CX" => {
+                for pair in qubits.chunks(2) {
+                    x[pair[1]] ^= x[pair[0]];
+                    z[pair[0]] ^= z[pair[1]];
+                }
+            }
+            "MR" => {
+                for (k, &q) in qubits.iter().enumerate() {
+                    records.push(x[q] ^ fault_on(k));
+                    x[q] = false;
+                    z[q] = false;
+                }
+            }
+            "MPP" => {
+                for t in &ins.targets {
+                    let (px, pz) = product(t, n);
+                    records
+                        .push((0..n).fold(false, |acc, q| acc ^ (px[q] && z[q]) ^ (pz[q] && x[q])));
+                }
+            }
+            "DETECTOR" => {
+                let flipped = ins
+                    .targets
+                    .iter()
+                    .fold(false, |acc, t| acc ^ records[record(t, records.len())]);
+                if flipped {
+                    detectors.push(detector);
+                }
+                detector += 1;
+            }
+            "OBSERVABLE_INCLUDE" => {
+                let flipped = ins
+                    .targets
+                    .iter()
+                    .fold(false, |acc, t| acc ^ records[record(t, records.len())]);
+                if flipped {
+                    observables.push(ins.argument.as_ref().unwrap().parse().unwrap());
+                }
+            }
+            name => panic!("unexpected instruction {}", name),
+        }
+    }
+    (detectors, observables)
+}
+
+// Every single fault of the circuit, with its probability as an independent
+// mechanism, and the detectors and observables it flips.
+fn faults(circuit: &str) -> Vec<(Vec<usize>, Vec<usize>, f64)> {
+    let instructions = parse(circuit);
+    let mut ret = vec![];
+    for (index, ins) in instructions.iter().enumerate() {
+        let Some(argument) = &ins.argument else {
+            continue;
+        };
+        let (paulis, probability): (&[&'static str], f64) = match ins.name.as_str() {
+            "DEPOLARIZE1" => {
+                let p: f64 = argument.parse().unwrap();
+                (&["X", "Y", "Z"], (1.0 - (1.0 - 4.0 * p / 3.0).sqrt()) / 2.0)
+            }
+            "MR" => (&["M"], argument.parse().unwrap()),
+            _ => continue,
+        };
+        for target in 0..ins.targets.len() {
+            for &pauli in paulis {
+                let fault = Fault {
+                    instruction: index,
+                    target,
+                    pauli,
+                };
+                let (detectors, observables) = signature(&instructions, &fault);
+                ret.push((detectors, observables, probability));
+            }
+        }
+    }
+    ret
+}
+
+#[test]
+fn dem_matches_circuit_faults() {
+    // the second input has fewer cycles than the generator keeps quiet
+    for input in ["5 7 0.01 0.02 1\n0\n0\n0\n", "3 2 0.03 0.05 1\n0\n0\n0\n"] {
+        let input = parse_input(input);
+        for (memory, observable) in [(Memory::Z, 0), (Memory::X, 1)] {
+            let mut expected: Vec<(Vec<usize>, Vec<usize>, f64)> = mechanisms(&input)
+                .iter()
+                .map(|m| {
+                    let mut observables = m.observables();
+                    observables.retain(|&o| o == observable);
+                    (m.detectors(), observables, m.probability)
+                })
+                .collect();
+            let mut actual = faults(&to_stim(&input, memory));
+            let key = |a: &(Vec<usize>, Vec<usize>, f64), b: &(Vec<usize>, Vec<usize>, f64)| {
+                (&a.0, &a.1, a.2).partial_cmp(&(&b.0, &b.1, b.2)).unwrap()
+            };
+            expected.sort_by(key);
+            actual.sort_by(key);
+            assert_eq!(expected.len(), actual.len(), "memory {}", memory.name());
+            for (e, a) in expected.iter().zip(&actual) {
+                assert_eq!((&e.0, &e.1), (&a.0, &a.1), "memory {}", memory.name());
+                assert!((e.2 - a.2).abs() <= 1e-12 * e.2, "memory {}", memory.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use rust::input::parse_input;
+use rust::syndrome::{syndrome_stream, Syndromes};
+
+// Case 0 of a 5 x 5 lattice: an X error at (2, 2) and a Z error at (0, 0) in
+// cycle 0, measurement errors at (2, 1) in cycle 0 and (3, 4) in cycle 1.
+// Case 1: an X error at (0, 0) in cycle 1.
+const INPUT: &str = "5 2 0.01 0.02 2
+2
+0 0 2 2
+1 1 0 0
+1
+0 0 0 0
+2
+0 0 2 1
+0 1 3 4
+";
+
+#[test]
+fn measured_is_neighbour_parity_with_measurement_errors() {
+    let input = parse_input(INPUT);
+    let syndromes = Syndromes::observe(&input, 0);
+    assert_eq!((syndromes.d, syndromes.c), (5, 2));
+    assert_eq!((syndromes.e_d, syndromes.e_m), (0.01, 0.02));
+
+    let instance = &input.testcase[0];
+    let (mut x, mut z) = (vec![vec![0; 5]; 5], vec![vec![0; 5]; 5]);
+    for cycle in 0..input.c {
+        for &(i, j) in &instance.x_depolarizing_error[cycle] {
+            x[i][j] ^= 1;
+        }
+        for &(i, j) in &instance.z_depolarizing_error[cycle] {
+            z[i][j] ^= 1;
+        }
+        for i in 0..5 {
+            for j in 0..5 {
+                let expected = if (i + j) % 2 == 0 {
+                    0
+                } else {
+                    // X ancillas at odd j see X errors, Z ancillas at even j Z errors
+                    let errors = if j % 2 == 1 { &x } else { &z };
+                    let mut parity = 0;
+                    for (di, dj) in [(0, -1), (0, 1), (1, 0), (-1, 0)] {
+                        let (ni, nj) = (i as i32 + di, j as i32 + dj);
+                        if (0..5).contains(&ni) && (0..5).contains(&nj) {
+                            parity ^= errors[ni as usize][nj as usize];
+                        }
+                    }
+                    let flipped = instance.measure_error[cycle].contains(&(i, j));
+                    parity ^ flipped as usize
+                };
+                assert_eq!(
+                    syndromes.measured[cycle][i][j], expected,
+                    "cycle {} ({}, {})",
+                    cycle, i, j
+                );
+            }
+        }
+    }
+
+    // the X error lights the X ancillas left and right of it, the Z error
+    // the Z ancilla below it; the measurement errors hide (2, 1) in cycle 0
+    // and add (3, 4) in cycle 1
+    let ones = |cycle: usize| {
+        let mut ret = vec![];
+        for (i, row) in syndromes.measured[cycle].iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                if v == 1 {
+                    ret.push((i, j));
+                }
+            }
+        }
+        ret
+    };
+    assert_eq!(ones(0), vec![(1, 0), (2, 3)]);
+    assert_eq!(ones(1), vec![(1, 0), (2, 1), (2, 3), (3, 4)]);
+}
+
+#[test]
+fn stream_lists_every_measured_one() {
+    let input = parse_input(INPUT);
+    assert_eq!(
+        syndrome_stream(&input),
+        "5 2 0.01 0.02 2
+7
+0 0 1 0
+0 0 2 3
+0 1 1 0
+0 1 2 1
+0 1 2 3
+0 1 3 4
+1 1 0 1
+"
+    );
+
+    // without errors only the header and a zero count
+    let quiet = parse_input("3 2 0 0 1\n0\n0\n0\n");
+    assert_eq!(syndrome_stream(&quiet), "3 2 0 0 1\n0\n");
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use std::collections::BTreeSet;
+
+use rust::heatmap::Aggregate;
+use rust::output::parse_output;
+use rust::theme::Theme;
+use rust::vis::{
+    get_max_union_find_step, vis_animation, vis_diff, vis_heatmap, vis_site_history, vis_summary,
+    vis_timeline, vis_union_find, vis_with, VisOptions,
+};
+
+// Classes that only group or mark elements and take no colour.
+const STRUCTURE: [&str; 6] = ["box", "turn", "panel", "hud", "legend", "union-find"];
+
+fn classes(svg: &str) -> BTreeSet<String> {
+    svg.split(" class=\"")
+        .skip(1)
+        .flat_map(|rest| rest[..rest.find('"').unwrap()].split_whitespace())
+        .map(|class| class.to_string())
+        .collect()
+}
+
+#[test]
+fn every_theme_has_a_name() {
+    for name in Theme::NAMES {
+        let theme = Theme::by_name(name).unwrap();
+        assert_eq!(theme.name, name);
+    }
+    assert_eq!(Theme::by_name("color-blind"), Some(Theme::colour_blind()));
+    assert_eq!(Theme::by_name("sepia"), None);
+    assert_eq!(Theme::default(), Theme::light());
+}
+
+#[test]
+fn themes_style_every_class_of_every_view() {
+    let (input, output) = common::decoded();
+    let empty = parse_output("", input.c, input.t);
+    let steps = get_max_union_find_step(&input, 0).unwrap();
+    for name in Theme::NAMES {
+        let theme = Theme::by_name(name).unwrap();
+        let options = VisOptions {
+            legend: true,
+            hud: true,
+            theme: theme.clone(),
+            ..VisOptions::default()
+        };
+        let mut svgs = vec![];
+        for turn in 0..input.c * 2 + 2 {
+            svgs.push(vis_with(&input, &output, turn, &options).2);
+            svgs.push(vis_diff(&input, &output, &empty, 0, turn, &theme).3);
+        }
+        svgs.push(vis_animation(&input, &output, 0, 0.5, &theme).2);
+        svgs.push(vis_timeline(&input, &output, 0, 0, &theme).2);
+        svgs.push(vis_site_history(&input, &output, 0, (0, 0), 0, &theme).2);
+        svgs.push(vis_site_history(&input, &output, 0, (0, 1), 0, &theme).2);
+        svgs.push(vis_summary(&input, &output, &theme).2);
+        svgs.push(vis_summary(&input, &empty, &theme).2);
+        for aggregate in Aggregate::ALL {
+            svgs.push(vis_heatmap(&input, &output, aggregate, &theme).2);
+        }
+        for step in 0..=steps {
+            svgs.push(vis_union_find(&input, &output, 0, 1, step, &theme).2);
+        }
+
+        let css = theme.css();
+        let mut used = BTreeSet::new();
+        for svg in &svgs {
+            assert!(!svg.is_empty(), "{}", name);
+            for class in classes(svg) {
+                // a view may style its own layout classes next to the theme
+                let styled = |style: &str| {
+                    style.contains(&format!(".{} ", class))
+                        || style.contains(&format!(".{}{{", class))
+                };
+                assert!(
+                    STRUCTURE.contains(&class.as_str()) || styled(&css) || styled(svg),
+                    "{}: .{} has no style",
+                    name,
+                    class
+                );
+                used.insert(class);
+            }
+        }
+        // the views above reach every colour of the theme
+        for class in [
+            "x",
+            "z",
+            "injected",
+            "correction",
+            "first",
+            "second",
+            "cluster-0",
+        ] {
+            assert!(used.contains(class), "{}: .{} unused", name, class);
+        }
+    }
+}
+
+#[test]
+fn dark_background_differs_from_light() {
+    let (input, output) = common::decoded();
+    let svg = |theme: Theme| {
+        let options = VisOptions {
+            theme,
+            ..VisOptions::default()
+        };
+        vis_with(&input, &output, 0, &options).2
+    };
+    let (light, dark) = (Theme::light(), Theme::dark());
+    assert_ne!(light.background, dark.background);
+    let background = |theme: &Theme| format!("#vis {{background-color: {}}}", theme.background);
+    let (light_svg, dark_svg) = (svg(light.clone()), svg(dark.clone()));
+    assert!(light_svg.contains(&background(&light)));
+    assert!(dark_svg.contains(&background(&dark)));
+    assert!(!dark_svg.contains(&background(&light)));
+}
@@ -0,0 +1,115 @@
+use rust::decoder;
+use rust::sample::{Estimate, Report};
+use rust::theme::Theme;
+use rust::threshold::{crossings, plot, sweep, to_csv, Point, SweepConfig};
+
+fn point(d: usize, e_d: f64, failures: usize, shots: usize) -> Point {
+    let estimate = Estimate { failures, shots };
+    Point {
+        d,
+        c: d + 5,
+        e_d,
+        e_m: e_d,
+        report: Report {
+            x: estimate,
+            z: estimate,
+            combined: estimate,
+        },
+    }
+}
+
+#[test]
+fn crossing_is_interpolated_in_log_log_space() {
+    // d = 5 is below d = 3 at 0.01 and above it at 0.04 by the same factor,
+    // so the curves cross halfway in log space; its zero rate at 0.02 is
+    // skipped rather than taken as a crossing.
+    let points = vec![
+        point(3, 0.01, 100, 1000),
+        point(3, 0.02, 200, 1000),
+        point(3, 0.04, 400, 1000),
+        point(5, 0.01, 50, 1000),
+        point(5, 0.02, 0, 1000),
+        point(5, 0.04, 800, 1000),
+    ];
+    let found = crossings(&points);
+    assert_eq!(found.len(), 1);
+    assert_eq!((found[0].d1, found[0].d2), (3, 5));
+    assert!((found[0].e_d - 0.02).abs() < 1e-9, "{}", found[0].e_d);
+}
+
+#[test]
+fn crossing_needs_the_larger_code_to_overtake() {
+    // the larger code starts worse and ends better: not a threshold
+    let points = vec![
+        point(3, 0.01, 50, 1000),
+        point(3, 0.04, 800, 1000),
+        point(5, 0.01, 100, 1000),
+        point(5, 0.04, 400, 1000),
+    ];
+    assert!(crossings(&points).is_empty());
+}
+
+#[test]
+fn csv_has_one_row_per_point() {
+    let points = vec![point(3, 0.01, 10, 100), point(5, 0.01, 0, 100)];
+    let csv = to_csv(&points, 1.96);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "d,c,e_d,e_m,shots,x_failures,z_failures,failures,rate,rate_low,rate_high"
+    );
+    let (lo, hi) = points[0].report.combined.wilson(1.96);
+    assert_eq!(
+        lines[1],
+        format!("3,8,0.01,0.01,100,10,10,10,0.1,{},{}", lo, hi)
+    );
+    let (lo, hi) = points[1].report.combined.wilson(1.96);
+    assert_eq!(
+        lines[2],
+        format!("5,10,0.01,0.01,100,0,0,0,0,{},{}", lo, hi)
+    );
+    assert_eq!(lines.len(), 3);
+}
+
+#[test]
+fn plot_has_a_series_per_distance_and_the_crossings() {
+    let points = vec![
+        point(3, 0.01, 100, 1000),
+        point(3, 0.04, 400, 1000),
+        point(5, 0.01, 50, 1000),
+        point(5, 0.04, 800, 1000),
+        point(7, 0.01, 20, 1000),
+        point(7, 0.04, 900, 1000),
+    ];
+    let theme = Theme::dark();
+    let svg = plot(&points, 1.96, &theme).to_string();
+    assert_eq!(svg.matches("class=\"series\"").count(), 3);
+    // 3 / 5 and 5 / 7 both cross between the two rates
+    assert_eq!(crossings(&points).len(), 2);
+    assert_eq!(svg.matches("class=\"crossing\"").count(), 2);
+    for k in 0..3 {
+        assert!(svg.contains(&format!("stroke=\"{}\"", theme.clusters[k])));
+    }
+    assert!(svg.contains(&format!("background-color:{}", theme.background)));
+}
+
+#[test]
+fn sweep_rejects_only_quiet_cycles() {
+    let decoder = decoder::find("union-find").unwrap();
+    let err = sweep(
+        decoder.as_ref(),
+        &SweepConfig {
+            distances: vec![3],
+            e_ds: vec![0.01],
+            e_m: None,
+            e_m_ratio: 1.0,
+            c: Some(4),
+            seed: 0,
+            max_shots: 10,
+            target: None,
+            z: 1.96,
+        },
+    )
+    .unwrap_err();
+    assert!(err.contains("quiet cycles"), "{}", err);
+}
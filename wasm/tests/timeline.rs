@@ -0,0 +1,113 @@
+mod common;
+
+use rust::graph::{DecodingGraph, Pauli};
+use rust::output::{parse_output, validate_output};
+use rust::score::residual;
+use rust::sdk::OutputBuilder;
+use rust::syndrome::Syndromes;
+use rust::theme::Theme;
+use rust::timeline::{site_history, timeline, SiteHistory};
+use rust::vis::{vis_site_history, vis_timeline};
+
+#[test]
+fn timeline_counts_match_the_case() {
+    let (input, output) = common::decoded();
+    let counts = timeline(&input, &output, 0);
+    assert_eq!(counts.len(), input.c);
+
+    let instance = &input.testcase[0];
+    assert!(instance.x_depolarizing_error.iter().any(|e| !e.is_empty()));
+    for (cycle, count) in counts.iter().enumerate() {
+        assert_eq!(count.x_errors, instance.x_depolarizing_error[cycle].len());
+        assert_eq!(count.z_errors, instance.z_depolarizing_error[cycle].len());
+        assert_eq!(
+            count.measurement_errors,
+            instance.measure_error[cycle].len()
+        );
+    }
+
+    let observed = Syndromes::observe(&input, 0);
+    let events: usize = [Pauli::X, Pauli::Z]
+        .iter()
+        .map(|&pauli| {
+            DecodingGraph::new(&input, pauli)
+                .detection_events(&observed)
+                .len()
+        })
+        .sum();
+    assert_eq!(
+        counts.iter().map(|c| c.detection_events).sum::<usize>(),
+        events
+    );
+
+    let last = residual(&input, &output, 0);
+    assert_eq!(
+        counts[input.c - 1].residual,
+        last.x.count_ones() + last.z.count_ones()
+    );
+}
+
+#[test]
+fn syndromes_without_corrections_are_observed() {
+    let (input, _) = common::decoded();
+    let output = OutputBuilder::for_input(&input).build();
+    let observed = Syndromes::observe(&input, 0);
+    for (cycle, count) in timeline(&input, &output, 0).iter().enumerate() {
+        let ones: usize = observed.measured[cycle].iter().flatten().sum();
+        assert_eq!(count.residual_syndromes, ones);
+        assert_eq!(count.corrections, 0);
+    }
+}
+
+#[test]
+fn site_histories_end_in_the_residual() {
+    let (input, output) = common::decoded();
+    let last = residual(&input, &output, 0);
+    for i in 0..input.d {
+        for j in 0..input.d {
+            match site_history(&input, &output, 0, i, j).unwrap() {
+                SiteHistory::Data(cycles) => {
+                    assert_eq!((i + j) % 2, 0);
+                    let end = &cycles[input.c - 1];
+                    assert_eq!(end.x_frame, last.x.get(i, j));
+                    assert_eq!(end.z_frame, last.z.get(i, j));
+                }
+                SiteHistory::Ancilla(pauli, cycles) => {
+                    assert!(pauli.is_ancilla(i, j));
+                    for c in &cycles {
+                        assert_eq!(
+                            c.residual_measured,
+                            c.residual_parity != c.measurement_error
+                        );
+                    }
+                }
+            }
+        }
+    }
+    assert!(site_history(&input, &output, 0, input.d, 0).is_err());
+    assert!(site_history(&input, &output, input.t, 0, 0).is_err());
+}
+
+#[test]
+fn site_histories_reject_an_invalid_output() {
+    // an X correction on row 3 of a 3 x 3 lattice
+    let input = common::quiet(3, 2, 1);
+    let output = parse_output("1\n0 0 3 0\n0\n", input.c, input.t);
+    let err = validate_output(&input, &output).unwrap_err();
+    assert_eq!(site_history(&input, &output, 0, 0, 0), Err(err.clone()));
+    let (_, shown, svg) = vis_site_history(&input, &output, 0, (0, 0), 0, &Theme::light());
+    assert_eq!(shown, err);
+    assert_eq!(svg, "");
+}
+
+#[test]
+fn charts_need_a_cycle() {
+    let input = common::quiet(3, 0, 1);
+    let output = OutputBuilder::for_input(&input).build();
+    let (_, err, svg) = vis_timeline(&input, &output, 0, 0, &Theme::light());
+    assert_eq!(err, "the input has no cycles");
+    assert_eq!(svg, "");
+    let (_, err, svg) = vis_site_history(&input, &output, 0, (1, 1), 0, &Theme::light());
+    assert_eq!(err, "the input has no cycles");
+    assert_eq!(svg, "");
+}
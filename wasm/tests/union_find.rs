@@ -0,0 +1,53 @@
+use rust::input::parse_input;
+use rust::output::parse_output;
+use rust::theme::Theme;
+use rust::vis::{get_max_union_find_step, vis_union_find};
+
+#[test]
+fn union_find_view_grows_clusters() {
+    // one X error in the middle of two noisy cycles
+    let input = parse_input("5 2 0.01 0.01 1\n1\n0 0 2 2\n0\n0\n");
+    let output = parse_output("", input.c, input.t);
+    let steps = get_max_union_find_step(&input, 0).unwrap();
+    assert!(steps > 0);
+    let (_, err, first) = vis_union_find(&input, &output, 0, 0, 0, &Theme::default());
+    assert_eq!(err, "");
+    let (_, err, last) = vis_union_find(&input, &output, 0, 0, steps, &Theme::default());
+    assert_eq!(err, "");
+    assert_ne!(first, last);
+}
+
+#[test]
+fn union_find_view_without_cycles() {
+    let input = parse_input("3 0 0.01 0.01 1\n0\n0\n0\n");
+    let output = parse_output("", input.c, input.t);
+    assert_eq!(get_max_union_find_step(&input, 0), Ok(0));
+    let (_, err, svg) = vis_union_find(&input, &output, 0, 0, 0, &Theme::default());
+    assert_eq!(err, "");
+    assert!(svg.starts_with("<svg"));
+}
+
+#[test]
+fn union_find_steps_reject_missing_case() {
+    let input = parse_input("3 1 0.01 0.01 0\n0\n0\n0\n");
+    assert!(get_max_union_find_step(&input, 0).is_err());
+    let input = parse_input("5 2 0.01 0.01 1\n1\n0 0 2 2\n0\n0\n");
+    assert!(get_max_union_find_step(&input, 1).is_err());
+}
+
+#[test]
+fn union_find_view_colours_clusters_by_theme() {
+    let input = parse_input("5 2 0.01 0.01 1\n1\n0 0 2 2\n0\n0\n");
+    let output = parse_output("", input.c, input.t);
+    let steps = get_max_union_find_step(&input, 0).unwrap();
+    for name in Theme::NAMES {
+        let theme = Theme::by_name(name).unwrap();
+        let (_, err, svg) = vis_union_find(&input, &output, 0, 0, steps, &theme);
+        assert_eq!(err, "");
+        assert!(svg.contains("cluster-0"), "{}", name);
+        assert!(svg.contains(&format!(".cluster-0 {{color: {}}}", theme.clusters[0])));
+        assert!(!svg.contains("hsl("));
+    }
+    let (_, err, _) = vis_union_find(&input, &output, 1, 0, 0, &Theme::default());
+    assert_eq!(err, "case 1 is out of range");
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use rust::frame::Frame;
+use rust::input::parse_input;
+use rust::output::parse_output;
+use rust::score::case_score;
+use rust::vis::{vis, vis_case, vis_with, VisOptions};
+
+fn view_box(svg: &str) -> &str {
+    let start = svg.find("viewBox=\"").unwrap() + "viewBox=\"".len();
+    &svg[start..start + svg[start..].find('"').unwrap()]
+}
+
+fn hud_line(svg: &str) -> &str {
+    let hud = svg.find("class=\"panel hud\"").unwrap();
+    let start = hud + svg[hud..].find("<text").unwrap();
+    let start = start + svg[start..].find('>').unwrap() + 1;
+    svg[start..start + svg[start..].find('<').unwrap()].trim()
+}
+
+#[test]
+fn panels_grow_the_view_box_only_when_asked() {
+    let input = parse_input("3 2 0 0 1\n1\n0 0 2 0\n0\n0\n");
+    let output = parse_output("", input.c, input.t);
+    let plain = vis(&input, &output, 1);
+    assert_eq!(plain.1, "");
+    assert_eq!(vis_with(&input, &output, 1, &VisOptions::default()), plain);
+    assert_eq!(vis_case(&input, &output, 0, 1), plain);
+    assert_eq!(view_box(&plain.2), "-5 -5 1010 1010");
+    assert!(!plain.2.contains("panel"));
+
+    for (legend, hud, expected) in [
+        (true, false, "-5 -5 1450 1010"),
+        (false, true, "-5 -55 1010 1060"),
+        (true, true, "-5 -55 1450 1060"),
+    ] {
+        let options = VisOptions {
+            legend,
+            hud,
+            ..VisOptions::default()
+        };
+        let (score, err, svg) = vis_with(&input, &output, 1, &options);
+        assert_eq!((score, err.as_str()), (plain.0, ""));
+        assert_eq!(view_box(&svg), expected);
+        assert_eq!(svg.contains("panel legend"), legend);
+        assert_eq!(svg.contains("panel hud"), hud);
+    }
+}
+
+#[test]
+fn hud_shows_the_state_of_the_turn() {
+    // an X error at (2, 0) in cycle 0, corrected in cycle 1, and a
+    // measurement error on the ancilla at (0, 1) in cycle 1
+    let input = parse_input("3 2 0 0 1\n1\n0 0 2 0\n0\n1\n0 1 0 1\n");
+    let output = parse_output("1\n0 1 2 0\n0\n", input.c, input.t);
+    let options = VisOptions {
+        hud: true,
+        ..VisOptions::default()
+    };
+    let mut error = Frame::new(input.d);
+    error.x.flip(2, 0);
+    let syndromes = error.syndrome().count_ones();
+    assert!(syndromes > 0);
+    let score = case_score(&error);
+    let clean = case_score(&Frame::new(input.d));
+
+    let expected = [
+        format!(
+            "case 0 · cycle 0 · errors · syndromes {} · residual 1 (X 1, Z 0) · score {}",
+            syndromes, score
+        ),
+        format!(
+            "case 0 · cycle 0 · corrections · syndromes {} · residual 1 (X 1, Z 0) · score {}",
+            syndromes, score
+        ),
+        // the measurement error lights an ancilla of its own in cycle 1
+        format!(
+            "case 0 · cycle 1 · errors · syndromes {} · residual 1 (X 1, Z 0) · score {}",
+            syndromes + 1,
+            score
+        ),
+        format!(
+            "case 0 · cycle 1 · corrections · syndromes 1 · residual 0 (X 0, Z 0) · score {}",
+            clean
+        ),
+        format!(
+            "case 0 · final measurement · syndromes 0 · residual 0 (X 0, Z 0) · score {}",
+            clean
+        ),
+        format!(
+            "case 0 · final sweep · syndromes 0 · residual 0 (X 0, Z 0) · score {}",
+            clean
+        ),
+    ];
+    for (turn, line) in expected.iter().enumerate() {
+        let (_, err, svg) = vis_with(&input, &output, turn, &options);
+        assert_eq!(err, "");
+        assert_eq!(hud_line(&svg), line, "turn {}", turn);
+    }
+}
+
+#[test]
+fn glyphs_fit_any_lattice_size() {
+    for d in [3, 5, 7, 9, 21, 23] {
+        // X and Z errors on the data qubit at (0, 0) and their corrections,
+        // and a measurement error on the ancilla right of it
+        let input = parse_input(&format!(
+            "{} 1 0 0 1\n1\n0 0 0 0\n1\n0 0 0 0\n1\n0 0 0 1\n",
+            d
+        ));
+        let output = parse_output("1\n0 0 0 0\n1\n0 0 0 0\n", input.c, input.t);
+        let (_, err, svg) = vis(&input, &output, 1);
+        assert_eq!(err, "");
+        // 1000 units of lattice with a margin of 5 all around
+        let (low, high) = (-5.0 - 0.01, 1005.0 + 0.01);
+        let inside = |v: f64| (low..=high).contains(&v);
+        let s = 1000.0 / d as f64;
+
+        let mut injected = vec![];
+        for (tag, attributes) in common::elements(&svg) {
+            let number = |key: &str| attributes[key].parse::<f64>().unwrap();
+            match tag.as_str() {
+                "rect" => {
+                    let half = number("stroke-width") / 2.0;
+                    let (x, y) = (number("x"), number("y"));
+                    let (width, height) = (number("width"), number("height"));
+                    assert!(inside(x - half) && inside(x + width + half), "d {}", d);
+                    assert!(inside(y - half) && inside(y + height + half), "d {}", d);
+                }
+                "circle" => {
+                    let reach = number("r") + number("stroke-width") / 2.0;
+                    let (cx, cy) = (number("cx"), number("cy"));
+                    assert!(inside(cx - reach) && inside(cx + reach), "d {}", d);
+                    assert!(inside(cy - reach) && inside(cy + reach), "d {}", d);
+                    if attributes["class"] == "box injected" {
+                        injected.push((cx, cy, reach));
+                    }
+                }
+                "path" => {
+                    let half = number("stroke-width") / 2.0;
+                    let start = attributes["d"].trim_start_matches('M');
+                    let mut point = start.split(|c: char| c == ',' || c.is_whitespace());
+                    let x: f64 = point.next().unwrap().parse().unwrap();
+                    let y: f64 = point.next().unwrap().parse().unwrap();
+                    assert!(inside(x - half) && inside(y - half), "d {}", d);
+                }
+                _ => {}
+            }
+        }
+
+        // drawn in the order X, Z, measurement; each stays in its cell and
+        // the markers keep apart when put into one cell
+        assert_eq!(injected.len(), 3, "d {}", d);
+        let corners = [(0.0, 0.0), (0.0, 0.0), (s, 0.0)];
+        let local: Vec<(f64, f64, f64)> = injected
+            .iter()
+            .zip(corners)
+            .map(|(&(cx, cy, reach), (x, y))| (cx - x, cy - y, reach))
+            .collect();
+        for &(cx, cy, reach) in &local {
+            assert!(cx - reach >= -0.01 && cx + reach <= s + 0.01, "d {}", d);
+            assert!(cy - reach >= -0.01 && cy + reach <= s + 0.01, "d {}", d);
+        }
+        let (measurement, z) = (local[2], local[1]);
+        assert!(
+            measurement.0 < s / 2.0 && measurement.1 > s / 2.0,
+            "d {}",
+            d
+        );
+        for (k, other) in local[..2].iter().enumerate() {
+            let distance =
+                ((measurement.0 - other.0).powi(2) + (measurement.1 - other.1).powi(2)).sqrt();
+            assert!(distance >= measurement.2 + other.2, "d {} marker {}", d, k);
+        }
+        assert!(z.0 > s / 2.0 && z.1 < s / 2.0, "d {}", d);
+    }
+}